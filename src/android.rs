@@ -0,0 +1,126 @@
+//! Reading of Android `strings.xml` resource files.
+use crate::printf;
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+
+/// Parses the content of an Android `strings.xml` resource file.
+/// The name of a `string` element is the key, its text is the value.
+/// Plurals and string arrays are not supported, they are an error.
+pub fn parse(content: &str) -> Result<HashMap<String, String>, String> {
+    let document = Document::parse(content).map_err(|e| e.to_string())?;
+    let root = document.root_element();
+    if root.tag_name().name() != "resources" {
+        return Err(format!(
+            "root element must be resources, but is {}",
+            root.tag_name().name()
+        ));
+    }
+
+    let mut result = HashMap::new();
+    for element in root.children().filter(Node::is_element) {
+        match element.tag_name().name() {
+            "string" => {}
+            "plurals" | "string-array" => {
+                return Err(format!(
+                    "{} elements are not supported, but '{}' is one",
+                    element.tag_name().name(),
+                    element.attribute("name").unwrap_or_default()
+                ));
+            }
+            _ => continue,
+        }
+
+        let name = element
+            .attribute("name")
+            .ok_or_else(|| format!("{} without name", element.tag_name().name()))?;
+
+        let mut raw = String::new();
+        append_text(element, &mut raw);
+        let mut value = unescape(&raw).map_err(|e| format!("string '{name}' {e}"))?;
+        if element.attribute("formatted") != Some("false") {
+            value = printf::convert(&value);
+        }
+
+        if result.insert(name.to_string(), value).is_some() {
+            return Err(format!("string '{name}' exists more than once"));
+        }
+    }
+
+    Ok(result)
+}
+
+/// Appends the text of an element to `result`, the content of styling tags like `<b>` is kept without the tags.
+fn append_text(element: Node, result: &mut String) {
+    for node in element.children() {
+        if node.is_text() {
+            result.push_str(node.text().unwrap_or_default());
+        } else if node.is_element() {
+            append_text(node, result);
+        }
+    }
+}
+
+/// Resolves the escape sequences and quotes of an Android string.
+/// Whitespace outside of double quotes is collapsed into a single space and removed at the start and end.
+fn unescape(raw: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(raw.len());
+    let mut quoted = false;
+    let mut pending_space = false;
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() && !quoted {
+            pending_space = true;
+            continue;
+        }
+
+        if pending_space && !result.is_empty() {
+            result.push(' ');
+        }
+        pending_space = false;
+
+        match c {
+            '"' => quoted = !quoted,
+            '\\' => match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .filter(|_| hex.len() == 4)
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| format!("has an invalid unicode escape \\u{hex}"))?;
+                    result.push(c);
+                }
+                Some(other) => result.push(other),
+                None => return Err("ends with a \\".to_string()),
+            },
+            other => result.push(other),
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn plurals_are_rejected() {
+        let content = "<resources><plurals name=\"photos\"><item quantity=\"other\">%d photos</item></plurals></resources>";
+        assert_eq!(
+            Err("plurals elements are not supported, but 'photos' is one".to_string()),
+            parse(content)
+        );
+    }
+
+    #[test]
+    fn string_arrays_are_rejected() {
+        let content = "<resources><string-array name=\"planets\"><item>Mercury</item></string-array></resources>";
+        assert_eq!(
+            Err("string-array elements are not supported, but 'planets' is one".to_string()),
+            parse(content)
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Reading of Apple `.strings` files.
+use crate::printf;
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Parses an Apple `.strings` file, which is either UTF-8 or UTF-16 with a byte order mark.
+/// The file consists of `"key" = "value";` entries and C style comments.
+pub fn parse(bytes: &[u8]) -> Result<HashMap<String, String>, String> {
+    let content = decode(bytes)?;
+    let mut chars = content.chars().peekable();
+    let mut result = HashMap::new();
+    loop {
+        skip_whitespace_and_comments(&mut chars)?;
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let key = parse_string(&mut chars)?;
+        skip_whitespace_and_comments(&mut chars)?;
+        let value = match chars.next() {
+            Some('=') => {
+                skip_whitespace_and_comments(&mut chars)?;
+                let value = parse_string(&mut chars)?;
+                skip_whitespace_and_comments(&mut chars)?;
+                if chars.next() != Some(';') {
+                    return Err(format!("expected ; after the value of key '{key}'"));
+                }
+                value
+            }
+            //Legacy entries without a value use the key as value.
+            Some(';') => key.clone(),
+            _ => return Err(format!("expected = after key '{key}'")),
+        };
+
+        if result
+            .insert(key.clone(), printf::convert(&value))
+            .is_some()
+        {
+            return Err(format!("key '{key}' exists more than once"));
+        }
+    }
+
+    Ok(result)
+}
+
+/// Decodes the content of the file.
+fn decode(bytes: &[u8]) -> Result<String, String> {
+    let utf16: fn([u8; 2]) -> u16 = match bytes {
+        [0xFF, 0xFE, ..] => u16::from_le_bytes,
+        [0xFE, 0xFF, ..] => u16::from_be_bytes,
+        [0xEF, 0xBB, 0xBF, rest @ ..] => {
+            return String::from_utf8(rest.to_vec()).map_err(|e| e.to_string())
+        }
+        _ => return String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string()),
+    };
+
+    let units = bytes[2..].chunks(2).map(|unit| match unit {
+        [a, b] => Ok(utf16([*a, *b])),
+        _ => Err("UTF-16 file has an odd length".to_string()),
+    });
+
+    let units = units.collect::<Result<Vec<u16>, String>>()?;
+    String::from_utf16(&units).map_err(|e| e.to_string())
+}
+
+/// Skips whitespace, `/* block */` and `// line` comments.
+fn skip_whitespace_and_comments(chars: &mut Peekable<Chars>) -> Result<(), String> {
+    loop {
+        match chars.peek() {
+            Some(c) if c.is_whitespace() => {
+                chars.next();
+            }
+            Some('/') => {
+                chars.next();
+                match chars.next() {
+                    Some('/') => {
+                        chars.by_ref().find(|c| *c == '\n');
+                    }
+                    Some('*') => {
+                        let mut previous = ' ';
+                        loop {
+                            let Some(c) = chars.next() else {
+                                return Err("unterminated comment".to_string());
+                            };
+                            if previous == '*' && c == '/' {
+                                break;
+                            }
+                            previous = c;
+                        }
+                    }
+                    _ => return Err("unexpected /".to_string()),
+                }
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// Parses a quoted string or an unquoted word.
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+    let mut result = String::new();
+    if chars.peek() != Some(&'"') {
+        while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || "_.-$:/".contains(*c)) {
+            result.push(c);
+        }
+
+        if result.is_empty() {
+            return Err(chars.peek().map_or_else(
+                || "unexpected end of file".to_string(),
+                |c| format!("unexpected {c}"),
+            ));
+        }
+
+        return Ok(result);
+    }
+
+    chars.next();
+    loop {
+        match chars.next() {
+            None => return Err(format!("unterminated string \"{result}")),
+            Some('"') => return Ok(result),
+            Some('\\') => match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some('r') => result.push('\r'),
+                Some('U' | 'u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .filter(|_| hex.len() == 4)
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| format!("invalid unicode escape \\U{hex}"))?;
+                    result.push(c);
+                }
+                Some(other) => result.push(other),
+                None => return Err(format!("unterminated string \"{result}")),
+            },
+            Some(other) => result.push(other),
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Tests of code that must not compile, they are run as doctests.
+
+/// Colliding names are a compiler error with `#[collisions = error]`.
+/// ```compile_fail
+/// pub enum Language {
+///     English,
+/// }
+///
+/// trivial_i18n::i18n! {
+///     Language;
+///     #[collisions = error]
+///     English="tests/collisions_english.properties";
+/// }
+///
+/// fn main() {}
+/// ```
+///
+/// Without colliding names, it compiles.
+/// ```
+/// pub enum Language {
+///     English,
+/// }
+///
+/// trivial_i18n::i18n! {
+///     Language;
+///     #[collisions = error]
+///     English="tests/collisions_strict.properties";
+/// }
+///
+/// fn main() {}
+/// ```
+struct Collisions;
+
+/// A cycle of message references is a compiler error.
+/// ```compile_fail
+/// pub enum Language {
+///     English,
+/// }
+///
+/// trivial_i18n::i18n! {
+///     Language;
+///     English="tests/reference_cycle.properties";
+/// }
+///
+/// fn main() {}
+/// ```
+///
+/// A message reference without a closing `}` is a compiler error.
+/// ```compile_fail
+/// pub enum Language {
+///     English,
+/// }
+///
+/// trivial_i18n::i18n! {
+///     Language;
+///     English="tests/reference_unterminated.properties";
+/// }
+///
+/// fn main() {}
+/// ```
+///
+/// References that can be resolved compile.
+/// ```
+/// pub enum Language {
+///     English,
+/// }
+///
+/// trivial_i18n::i18n! {
+///     Language;
+///     English="tests/reference_english.properties";
+/// }
+///
+/// fn main() {}
+/// ```
+struct MessageReferences;
+
+/// Using a deprecated key is a warning, so it is an error with `#![deny(deprecated)]`.
+/// ```compile_fail
+/// #![deny(deprecated)]
+///
+/// mod i18n {
+///     pub enum Language {
+///         English,
+///     }
+///
+///     trivial_i18n::i18n! {
+///         Language;
+///         English="tests/deprecation_english.properties";
+///     }
+/// }
+///
+/// fn main() {
+///     let _ = i18n::OLD_TITLE.as_str();
+/// }
+/// ```
+///
+/// Keys that are not deprecated can be used.
+/// ```
+/// #![deny(deprecated)]
+///
+/// mod i18n {
+///     pub enum Language {
+///         English,
+///     }
+///
+///     trivial_i18n::i18n! {
+///         Language;
+///         English="tests/deprecation_english.properties";
+///     }
+/// }
+///
+/// fn main() {
+///     let _ = i18n::NEW_TITLE.as_str();
+/// }
+/// ```
+struct Deprecation;
+
+/// Unused keys are reported with `#[unused = warn]`, so they are an error with `#![deny(dead_code)]`.
+/// ```compile_fail
+/// #![deny(dead_code)]
+///
+/// mod i18n {
+///     pub enum Language {
+///         English,
+///     }
+///
+///     trivial_i18n::i18n! {
+///         Language;
+///         #[unused = warn]
+///         #[visibility = pub(crate)]
+///         #[naming = screaming_snake]
+///         English="tests/unused_english.properties";
+///     }
+/// }
+///
+/// fn main() {
+///     i18n::set_i18n_language(i18n::Language::English);
+///     let _ = i18n::GREETING.as_str();
+/// }
+/// ```
+///
+/// If all keys are used, it compiles.
+/// ```
+/// #![deny(dead_code)]
+///
+/// mod i18n {
+///     pub enum Language {
+///         English,
+///     }
+///
+///     trivial_i18n::i18n! {
+///         Language;
+///         #[unused = warn]
+///         #[visibility = pub(crate)]
+///         #[naming = screaming_snake]
+///         English="tests/unused_english.properties";
+///     }
+/// }
+///
+/// fn main() {
+///     i18n::set_i18n_language(i18n::Language::English);
+///     let _ = i18n::GREETING.as_str();
+///     let _ = i18n::FAREWELL.as_str();
+/// }
+/// ```
+struct UnusedKeys;
+
+/// Statics whose name starts with `_` are never reported by rustc, so `#[unused = warn]` rejects them,
+/// like the escaped dotted key `_menu_DOT_open`.
+/// ```compile_fail
+/// mod i18n {
+///     pub enum Language {
+///         English,
+///     }
+///
+///     trivial_i18n::i18n! {
+///         Language;
+///         #[unused = warn]
+///         #[visibility = pub(crate)]
+///         English="tests/unused_dotted_english.properties";
+///     }
+/// }
+///
+/// fn main() {}
+/// ```
+///
+/// With another naming, the unused dotted key is reported.
+/// ```compile_fail
+/// #![deny(dead_code)]
+///
+/// mod i18n {
+///     pub enum Language {
+///         English,
+///     }
+///
+///     trivial_i18n::i18n! {
+///         Language;
+///         #[unused = warn]
+///         #[visibility = pub(crate)]
+///         #[naming = screaming_snake]
+///         English="tests/unused_dotted_english.properties";
+///     }
+/// }
+///
+/// fn main() {
+///     i18n::set_i18n_language(i18n::Language::English);
+///     let _ = i18n::GREETING.as_str();
+/// }
+/// ```
+///
+/// If it is used, it compiles.
+/// ```
+/// #![deny(dead_code)]
+///
+/// mod i18n {
+///     pub enum Language {
+///         English,
+///     }
+///
+///     trivial_i18n::i18n! {
+///         Language;
+///         #[unused = warn]
+///         #[visibility = pub(crate)]
+///         #[naming = screaming_snake]
+///         English="tests/unused_dotted_english.properties";
+///     }
+/// }
+///
+/// fn main() {
+///     i18n::set_i18n_language(i18n::Language::English);
+///     let _ = i18n::GREETING.as_str();
+///     let _ = i18n::MENU_OPEN.as_str();
+/// }
+/// ```
+struct UnusedEscapedKeys;
+
+/// Unused public items are not reported, so `#[unused = warn]` requires `#[visibility = pub(crate)]`.
+/// ```compile_fail
+/// mod i18n {
+///     pub enum Language {
+///         English,
+///     }
+///
+///     trivial_i18n::i18n! {
+///         Language;
+///         #[unused = warn]
+///         English="tests/unused_english.properties";
+///     }
+/// }
+///
+/// fn main() {}
+/// ```
+struct UnusedPublicKeys;
+
+/// Items that are generated with `#[visibility = pub(crate)]` cannot be used by other crates.
+/// ```compile_fail
+/// fn main() {
+///     let _ = visibility_lib::i18n::COLOR.as_str();
+/// }
+/// ```
+///
+/// ```compile_fail
+/// fn main() {
+///     visibility_lib::i18n::set_i18n_language(visibility_lib::i18n::Language::German);
+/// }
+/// ```
+///
+/// The crate that invokes the macro can use them.
+/// ```
+/// fn main() {
+///     assert_eq!("Farbe", visibility_lib::color(visibility_lib::i18n::Language::German));
+/// }
+/// ```
+struct CrateVisibility;
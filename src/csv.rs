@@ -0,0 +1,81 @@
+//! Reading of CSV and TSV tables that contain the values of all languages.
+use ::csv::ReaderBuilder;
+use std::collections::{HashMap, HashSet};
+
+/// A CSV or TSV table with a column per language.
+pub struct Table {
+    /// Names of the columns, the first column contains the keys and has no values.
+    names: Vec<String>,
+    /// Values of the columns by key, in the same order as the names.
+    columns: Vec<HashMap<String, String>>,
+}
+
+impl Table {
+    /// Parses a CSV or TSV table.
+    /// The first row contains the names of the columns, the first column contains the keys.
+    /// Empty cells are treated as not translated.
+    /// Quotes are only supported in CSV tables, the cells of TSV tables are read as they are.
+    pub fn parse(content: &str, delimiter: u8) -> Result<Self, String> {
+        let mut reader = ReaderBuilder::new()
+            .delimiter(delimiter)
+            .quoting(delimiter != b'\t')
+            .flexible(true)
+            .from_reader(content.as_bytes());
+
+        let names = reader
+            .headers()
+            .map_err(|e| e.to_string())?
+            .iter()
+            .map(|name| name.trim().to_string())
+            .collect::<Vec<_>>();
+        let mut columns = vec![HashMap::new(); names.len()];
+
+        let mut keys = HashSet::new();
+        for record in reader.records() {
+            let record = record.map_err(|e| e.to_string())?;
+            let key = record.get(0).unwrap_or_default();
+            if key.is_empty() {
+                continue;
+            }
+
+            if !keys.insert(key.to_string()) {
+                return Err(format!("key '{key}' exists more than once"));
+            }
+
+            for (column, value) in columns.iter_mut().zip(record.iter()).skip(1) {
+                if !value.is_empty() {
+                    column.insert(key.to_string(), value.to_string());
+                }
+            }
+        }
+
+        Ok(Self { names, columns })
+    }
+
+    /// Gets the values of the column with the given name, the first column with the keys is not considered.
+    pub fn column(&self, column: &str) -> Result<HashMap<String, String>, String> {
+        let mut indices = self
+            .names
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, name)| *name == column)
+            .map(|(index, _)| index);
+
+        let Some(index) = indices.next() else {
+            let names = self.names.iter().skip(1).cloned().collect::<Vec<_>>();
+            return Err(format!(
+                "the table has no column named '{column}', the columns are: {}",
+                names.join(", ")
+            ));
+        };
+
+        if indices.next().is_some() {
+            return Err(format!(
+                "the table has more than one column named '{column}'"
+            ));
+        }
+
+        Ok(self.columns[index].clone())
+    }
+}
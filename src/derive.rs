@@ -0,0 +1,335 @@
+//! Translation of the language enum of `#[derive(I18n)]` into the input of the `i18n!` macro.
+use crate::parse_string_literal;
+use proc_macro::token_stream::IntoIter;
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::iter::Peekable;
+
+/// A language of the enum, declared by a variant and its `#[i18n(...)]` attributes.
+struct LanguageDeclaration {
+    /// Name of the variant.
+    name: Ident,
+    /// Paths of the files of the language, relative to the `dir` of the enum.
+    files: Vec<String>,
+    /// Tokens of the length expansion of a pseudo language, empty if it has the default expansion.
+    pseudo: Option<Vec<TokenTree>>,
+    /// Fallback languages.
+    fallbacks: Vec<TokenTree>,
+    /// Attributes that are passed on as they are, like `rtl` or `name = "..."`.
+    attributes: Vec<Vec<TokenTree>>,
+    /// Is this the default language.
+    default: bool,
+}
+
+/// Translates the language enum into the input of the `i18n!` macro.
+/// The arguments of the `#[i18n(...)]` attribute of the enum are the options,
+/// except `dir`, which is the directory of the files of the languages.
+pub fn translate(input: TokenStream) -> TokenStream {
+    let mut token_iter = input.into_iter().peekable();
+    let mut options = Vec::new();
+
+    let language_name = loop {
+        match token_iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '#' => {
+                options.extend(parse_i18n_attribute(&mut token_iter));
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "enum" => {
+                let Some(TokenTree::Ident(name)) = token_iter.next() else {
+                    panic!("Trying to parse name of the language enum, a Ident, but got something else");
+                };
+                break name;
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => {
+                panic!("I18n can only be derived for enums, but got a struct");
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "union" => {
+                panic!("I18n can only be derived for enums, but got a union");
+            }
+            // Visibility
+            Some(_) => {}
+            None => panic!("Trying to parse language enum, but got nothing"),
+        }
+    };
+
+    let body = match token_iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group.stream(),
+        _ => panic!("Trying to parse variants of language enum {language_name}, but got something else, generic enums are not supported"),
+    };
+
+    let dir = take_dir_option(&mut options);
+    // Languages without a file read their column from the table
+    let table = options
+        .iter()
+        .any(|option| option.first().map(ToString::to_string).as_deref() == Some("table"));
+    let mut languages = parse_languages(body);
+    assert!(
+        !languages.is_empty(),
+        "Language enum {language_name} has no variants with an i18n attribute"
+    );
+
+    let defaults = languages.iter().filter(|language| language.default).count();
+    assert!(
+        defaults <= 1,
+        "Language enum {language_name} has {defaults} default languages, but only one is allowed"
+    );
+    // The default language is the first language of the i18n! macro
+    if let Some(idx) = languages.iter().position(|language| language.default) {
+        let default = languages.remove(idx);
+        languages.insert(0, default);
+    }
+
+    let mut output = vec![
+        TokenTree::Ident(language_name),
+        TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+    ];
+
+    for option in options {
+        output.push(TokenTree::Punct(Punct::new('#', Spacing::Alone)));
+        output.push(TokenTree::Group(Group::new(
+            Delimiter::Bracket,
+            option.into_iter().collect(),
+        )));
+    }
+
+    for language in languages {
+        push_language(language, dir.as_deref(), table, &mut output);
+    }
+
+    output.into_iter().collect()
+}
+
+/// Parses the `[...]` part of an attribute after the `#`.
+/// Returns the comma separated arguments if it is an `#[i18n(...)]` attribute, or nothing for all other attributes.
+fn parse_i18n_attribute(token_iter: &mut Peekable<IntoIter>) -> Vec<Vec<TokenTree>> {
+    let Some(TokenTree::Group(attribute)) = token_iter.next() else {
+        panic!("Trying to parse attribute after #, but got something else");
+    };
+
+    let mut attribute_iter = attribute.stream().into_iter();
+    match attribute_iter.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "i18n" => {}
+        _ => return Vec::new(),
+    }
+
+    match attribute_iter.next() {
+        Some(TokenTree::Group(arguments)) if arguments.delimiter() == Delimiter::Parenthesis => {
+            split_arguments(arguments.stream())
+        }
+        _ => panic!(
+            "Trying to parse the arguments of the i18n attribute in (), but got something else"
+        ),
+    }
+}
+
+/// Splits the arguments of an attribute at the commas.
+fn split_arguments(stream: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut arguments = Vec::new();
+    let mut current = Vec::new();
+    for next in stream {
+        match next {
+            TokenTree::Punct(p) if p.as_char() == ',' => {
+                if !current.is_empty() {
+                    arguments.push(std::mem::take(&mut current));
+                }
+            }
+            other => current.push(other),
+        }
+    }
+
+    if !current.is_empty() {
+        arguments.push(current);
+    }
+
+    arguments
+}
+
+/// Removes the `dir` option from the options of the enum and returns its value.
+fn take_dir_option(options: &mut Vec<Vec<TokenTree>>) -> Option<String> {
+    let mut dir = None;
+    options.retain(|option| {
+        let name = option.first().map(ToString::to_string);
+        match name.as_deref() {
+            Some("pattern") => {
+                panic!("Option pattern is not supported by I18n, every language needs a file");
+            }
+            Some("dir") => {}
+            _ => return true,
+        }
+
+        let value = match option.as_slice() {
+            [_, TokenTree::Punct(p), TokenTree::Literal(value)] if p.as_char() == '=' => {
+                parse_string_literal(&value.to_string())
+            }
+            _ => None,
+        };
+        dir = Some(value.unwrap_or_else(|| {
+            panic!(
+                "Option dir must be a string literal, but got {}",
+                stringify(option)
+            )
+        }));
+        false
+    });
+
+    dir
+}
+
+/// Parses the variants of the language enum that have `#[i18n(...)]` attributes.
+fn parse_languages(body: TokenStream) -> Vec<LanguageDeclaration> {
+    let mut languages = Vec::new();
+    let mut arguments = Vec::new();
+    let mut token_iter = body.into_iter().peekable();
+
+    while let Some(next) = token_iter.next() {
+        match next {
+            TokenTree::Punct(p) if p.as_char() == '#' => {
+                arguments.extend(parse_i18n_attribute(&mut token_iter));
+            }
+            TokenTree::Ident(name) => {
+                match token_iter.peek() {
+                    Some(TokenTree::Group(_)) => {
+                        panic!("Language {name} must be a variant without fields")
+                    }
+                    // Skip the discriminant
+                    Some(TokenTree::Punct(p)) if p.as_char() == '=' => {
+                        while token_iter
+                            .next_if(
+                                |next| !matches!(next, TokenTree::Punct(p) if p.as_char() == ','),
+                            )
+                            .is_some()
+                        {}
+                    }
+                    _ => {}
+                }
+
+                // Variants without attributes are not declared, they use the default language
+                let arguments = std::mem::take(&mut arguments);
+                if !arguments.is_empty() {
+                    languages.push(parse_language(name, arguments));
+                }
+            }
+            TokenTree::Punct(p) if p.as_char() == ',' => {}
+            other => panic!("Trying to parse variant of language enum, but got {other}"),
+        }
+    }
+
+    languages
+}
+
+/// Parses the arguments of the `#[i18n(...)]` attributes of a variant.
+fn parse_language(name: Ident, arguments: Vec<Vec<TokenTree>>) -> LanguageDeclaration {
+    let mut language = LanguageDeclaration {
+        name,
+        files: Vec::new(),
+        pseudo: None,
+        fallbacks: Vec::new(),
+        attributes: Vec::new(),
+        default: false,
+    };
+
+    for argument in arguments {
+        let argument_name = argument.first().map(ToString::to_string);
+        match (argument_name.as_deref(), argument.as_slice()) {
+            (Some("default"), [_]) => language.default = true,
+            (Some("pseudo"), [_]) => language.pseudo = Some(Vec::new()),
+            (Some("pseudo"), [_, TokenTree::Punct(p), expansion @ ..]) if p.as_char() == '=' => {
+                language.pseudo = Some(expansion.to_vec());
+            }
+            (Some("file"), [_, TokenTree::Punct(p), TokenTree::Literal(file)])
+                if p.as_char() == '=' =>
+            {
+                language
+                    .files
+                    .push(parse_string_literal(&file.to_string()).unwrap_or_else(|| {
+                        panic!(
+                        "Attribute file of language {} must be a string literal, but got {file}",
+                        language.name
+                    )
+                    }));
+            }
+            (Some("fallback"), [_, TokenTree::Punct(p), fallback @ TokenTree::Ident(_)])
+                if p.as_char() == '=' =>
+            {
+                language.fallbacks.push(fallback.clone());
+            }
+            (Some("default" | "pseudo" | "file" | "fallback"), _) => panic!(
+                "Language {} has invalid attribute {}",
+                language.name,
+                stringify(&argument)
+            ),
+            _ => language.attributes.push(argument),
+        }
+    }
+
+    language
+}
+
+/// Appends the declaration of a language in the syntax of the `i18n!` macro.
+/// If `table` is true, languages without a file are declared without a path.
+fn push_language(
+    language: LanguageDeclaration,
+    dir: Option<&str>,
+    table: bool,
+    output: &mut Vec<TokenTree>,
+) {
+    let name = language.name.to_string();
+    output.push(TokenTree::Ident(language.name));
+
+    if let Some(expansion) = language.pseudo {
+        assert!(
+            language.files.is_empty(),
+            "Pseudo language {name} cannot have files"
+        );
+        output.push(TokenTree::Punct(Punct::new('=', Spacing::Alone)));
+        output.push(TokenTree::Ident(Ident::new("pseudo", Span::call_site())));
+        if !expansion.is_empty() {
+            let expansion = expansion.into_iter().collect();
+            output.push(TokenTree::Group(Group::new(
+                Delimiter::Parenthesis,
+                expansion,
+            )));
+        }
+    } else if !language.files.is_empty() {
+        output.push(TokenTree::Punct(Punct::new('=', Spacing::Alone)));
+        for (idx, file) in language.files.iter().enumerate() {
+            if idx > 0 {
+                output.push(TokenTree::Punct(Punct::new('+', Spacing::Alone)));
+            }
+
+            let path = dir.map_or_else(|| file.clone(), |dir| format!("{dir}/{file}"));
+            output.push(TokenTree::Literal(Literal::string(&path)));
+        }
+    } else {
+        // The column of the language is read from the table
+        assert!(
+            table,
+            "Language {name} needs a file, declare it with #[i18n(file = \"...\")] or use the table option"
+        );
+    }
+
+    for fallback in language.fallbacks {
+        output.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+        output.push(fallback);
+    }
+
+    if !language.attributes.is_empty() {
+        let mut attributes = Vec::new();
+        for (idx, attribute) in language.attributes.into_iter().enumerate() {
+            if idx > 0 {
+                attributes.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+            }
+            attributes.extend(attribute);
+        }
+
+        output.push(TokenTree::Group(Group::new(
+            Delimiter::Bracket,
+            attributes.into_iter().collect(),
+        )));
+    }
+
+    output.push(TokenTree::Punct(Punct::new(';', Spacing::Alone)));
+}
+
+/// Formats tokens as they appear in the source code, for error messages.
+fn stringify(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
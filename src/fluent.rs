@@ -0,0 +1,169 @@
+//! Reading of Project Fluent .ftl files.
+use crate::variables;
+use fluent_syntax::ast::{Entry, Expression, InlineExpression, Pattern, PatternElement, Term};
+use fluent_syntax::unicode::unescape_unicode_to_string;
+use std::collections::HashMap;
+
+/// Parses the content of a .ftl file.
+/// Messages are mapped to keys by their id, attributes of messages to `id.attribute`.
+/// Terms are inlined, message references are converted to `${id}` message references.
+pub fn parse(content: &str) -> Result<HashMap<String, String>, String> {
+    let resource = fluent_syntax::parser::parse(content).map_err(|(_, errors)| {
+        errors
+            .iter()
+            .map(|e| format!("line {}: {e}", line_number(content, e.pos.start)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    })?;
+
+    let mut terms = HashMap::new();
+    for entry in &resource.body {
+        if let Entry::Term(term) = entry {
+            terms.insert(term.id.name, term);
+        }
+    }
+
+    let mut result = HashMap::new();
+    for entry in &resource.body {
+        let Entry::Message(message) = entry else {
+            continue;
+        };
+
+        let id = message.id.name;
+        let mut entries = Vec::new();
+        if let Some(value) = &message.value {
+            entries.push((id.to_string(), value));
+        }
+
+        for attribute in &message.attributes {
+            entries.push((format!("{id}.{}", attribute.id.name), &attribute.value));
+        }
+
+        for (key, pattern) in entries {
+            let mut value = String::new();
+            convert_pattern(pattern, &terms, &mut Vec::new(), &mut value)
+                .map_err(|e| format!("message '{key}': {e}"))?;
+
+            if result.insert(key.clone(), value).is_some() {
+                return Err(format!("message '{key}' exists more than once"));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Gets the line number of a byte offset in the content.
+fn line_number(content: &str, offset: usize) -> usize {
+    content
+        .get(..offset)
+        .map_or(0, |before| before.matches('\n').count())
+        + 1
+}
+
+/// Converts a fluent pattern into a value and appends it to `result`.
+/// `stack` contains the terms that are currently being inlined and is used to detect cycles.
+fn convert_pattern<'s>(
+    pattern: &Pattern<&'s str>,
+    terms: &HashMap<&'s str, &Term<&'s str>>,
+    stack: &mut Vec<&'s str>,
+    result: &mut String,
+) -> Result<(), String> {
+    for element in &pattern.elements {
+        match element {
+            PatternElement::TextElement { value } => result.push_str(value),
+            PatternElement::Placeable { expression } => {
+                convert_expression(expression, terms, stack, result)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts a fluent placeable expression and appends it to `result`.
+fn convert_expression<'s>(
+    expression: &Expression<&'s str>,
+    terms: &HashMap<&'s str, &Term<&'s str>>,
+    stack: &mut Vec<&'s str>,
+    result: &mut String,
+) -> Result<(), String> {
+    let inline = match expression {
+        Expression::Select { .. } => {
+            return Err(
+                "selectors are not supported because they cannot be resolved at compile time"
+                    .to_string(),
+            );
+        }
+        Expression::Inline(inline) => inline,
+    };
+
+    match inline {
+        InlineExpression::StringLiteral { value } => {
+            result.push_str(&unescape_unicode_to_string(value));
+        }
+        InlineExpression::NumberLiteral { value } => result.push_str(value),
+        InlineExpression::FunctionReference { id, .. } => {
+            return Err(format!(
+                "function {}() is not supported because it cannot be resolved at compile time",
+                id.name
+            ));
+        }
+        InlineExpression::MessageReference { id, attribute } => {
+            result.push_str("${");
+            result.push_str(id.name);
+            if let Some(attribute) = attribute {
+                result.push('.');
+                result.push_str(attribute.name);
+            }
+            result.push('}');
+        }
+        InlineExpression::TermReference {
+            id,
+            attribute,
+            arguments,
+        } => {
+            if arguments.is_some() {
+                return Err(format!(
+                    "term -{} is called with arguments, parameterized terms are not supported",
+                    id.name
+                ));
+            }
+
+            if stack.contains(&id.name) {
+                return Err(format!("term -{} references itself", id.name));
+            }
+
+            let term = terms
+                .get(id.name)
+                .ok_or_else(|| format!("term -{} does not exist", id.name))?;
+
+            let pattern = match attribute {
+                Some(attribute) => term
+                    .attributes
+                    .iter()
+                    .find(|candidate| candidate.id.name == attribute.name)
+                    .map(|attribute| &attribute.value)
+                    .ok_or_else(|| {
+                        format!(
+                            "term -{} does not have the attribute {}",
+                            id.name, attribute.name
+                        )
+                    })?,
+                None => &term.value,
+            };
+
+            stack.push(id.name);
+            convert_pattern(pattern, terms, stack, result)?;
+            stack.pop();
+        }
+        InlineExpression::VariableReference { id } => {
+            variables::push(id.name, result);
+        }
+        InlineExpression::Placeable { expression } => {
+            convert_expression(expression, terms, stack, result)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,305 @@
+//! Reading of GNU gettext .po and .mo files.
+use std::collections::{BTreeMap, HashMap};
+
+/// Magic number at the start of a .mo file.
+const MO_MAGIC: u32 = 0x9504_12DE;
+
+/// The part of a .po entry that continuation lines are appended to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Field {
+    /// `msgctxt`
+    Context,
+    /// `msgid`
+    Id,
+    /// `msgstr` or `msgstr[n]`
+    Translation(usize),
+}
+
+/// A single entry of a .po file.
+#[derive(Debug, Default)]
+struct Entry {
+    /// `msgctxt`
+    context: Option<String>,
+    /// `msgid`
+    id: Option<String>,
+    /// `msgstr` or `msgstr[n]` by n.
+    translations: BTreeMap<usize, String>,
+    /// Is the entry marked as fuzzy
+    fuzzy: bool,
+}
+
+/// Settings that control which entries of a gettext file are used.
+#[derive(Debug, Copy, Clone)]
+pub struct Settings {
+    /// Use entries that are marked as fuzzy.
+    pub include_fuzzy: bool,
+    /// Use the msgid as value for entries without a translation.
+    /// Otherwise, such entries are treated as not translated.
+    pub untranslated_as_id: bool,
+}
+
+/// Parses the content of a .po file.
+/// The key of an entry is its msgid, or `msgctxt.msgid` if the entry has a context.
+/// Plural entries are not supported, they are an error.
+pub fn parse_po(content: &str, settings: Settings) -> Result<HashMap<String, String>, String> {
+    let mut result = HashMap::new();
+    let mut entry = Entry::default();
+    let mut field = None;
+
+    for (line_number, line) in content.lines().enumerate() {
+        let line_number = line_number + 1;
+        let line = line.trim();
+
+        if line.starts_with("#~") {
+            continue;
+        }
+
+        if line.is_empty() || line.starts_with('#') {
+            if !entry.translations.is_empty() {
+                finish_entry(&mut entry, settings, &mut result)?;
+                field = None;
+            }
+
+            if let Some(flags) = line.strip_prefix("#,") {
+                entry.fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            }
+            continue;
+        }
+
+        if line.starts_with('"') {
+            let value = parse_po_string(line).map_err(|e| format!("line {line_number}: {e}"))?;
+            let target = match field {
+                Some(Field::Context) => entry.context.as_mut(),
+                Some(Field::Id) => entry.id.as_mut(),
+                Some(Field::Translation(n)) => entry.translations.get_mut(&n),
+                None => return Err(format!("line {line_number}: string without keyword")),
+            };
+
+            if let Some(target) = target {
+                target.push_str(&value);
+            }
+            continue;
+        }
+
+        let Some((keyword, rest)) = line.split_once(|c: char| c.is_whitespace()) else {
+            return Err(format!("line {line_number}: expected keyword and string"));
+        };
+
+        let value = parse_po_string(rest.trim()).map_err(|e| format!("line {line_number}: {e}"))?;
+
+        let next_field = match keyword {
+            "msgctxt" => Field::Context,
+            "msgid" => Field::Id,
+            "msgid_plural" => {
+                return Err(format!(
+                    "line {line_number}: plural entries are not supported, but msgid {:?} has the plural {value:?}",
+                    entry.id.unwrap_or_default()
+                ));
+            }
+            "msgstr" => Field::Translation(0),
+            other => {
+                let index = other
+                    .strip_prefix("msgstr[")
+                    .and_then(|index| index.strip_suffix(']'))
+                    .and_then(|index| index.parse::<usize>().ok())
+                    .ok_or_else(|| format!("line {line_number}: unknown keyword {other}"))?;
+                Field::Translation(index)
+            }
+        };
+
+        if matches!(next_field, Field::Context | Field::Id) && !entry.translations.is_empty() {
+            finish_entry(&mut entry, settings, &mut result)?;
+        }
+
+        match next_field {
+            Field::Context => entry.context = Some(value),
+            Field::Id => entry.id = Some(value),
+            Field::Translation(n) => {
+                entry.translations.insert(n, value);
+            }
+        }
+
+        field = Some(next_field);
+    }
+
+    finish_entry(&mut entry, settings, &mut result)?;
+    Ok(result)
+}
+
+/// Adds a completely parsed .po entry to the result and resets it.
+fn finish_entry(
+    entry: &mut Entry,
+    settings: Settings,
+    result: &mut HashMap<String, String>,
+) -> Result<(), String> {
+    let entry = std::mem::take(entry);
+    let Some(id) = entry.id else {
+        return Ok(());
+    };
+
+    let translation = entry.translations.into_values().next().unwrap_or_default();
+    insert_entry(
+        entry.context,
+        id,
+        translation,
+        entry.fuzzy,
+        settings,
+        result,
+    )
+}
+
+/// Adds a single gettext entry to the result if it should be used.
+fn insert_entry(
+    context: Option<String>,
+    id: String,
+    translation: String,
+    fuzzy: bool,
+    settings: Settings,
+    result: &mut HashMap<String, String>,
+) -> Result<(), String> {
+    if id.is_empty() && context.is_none() {
+        //The header entry
+        return Ok(());
+    }
+
+    if fuzzy && !settings.include_fuzzy {
+        return Ok(());
+    }
+
+    let value = if !translation.is_empty() {
+        translation
+    } else if settings.untranslated_as_id {
+        id.clone()
+    } else {
+        return Ok(());
+    };
+
+    let key = match context {
+        Some(context) => format!("{context}.{id}"),
+        None => id,
+    };
+
+    if result.contains_key(&key) {
+        return Err(format!("key '{key}' exists more than once"));
+    }
+
+    result.insert(key, value);
+    Ok(())
+}
+
+/// Parses a quoted .po string and resolves its escape sequences.
+fn parse_po_string(quoted: &str) -> Result<String, String> {
+    let content = quoted
+        .strip_prefix('"')
+        .and_then(|content| content.strip_suffix('"'))
+        .ok_or_else(|| format!("expected quoted string, got {quoted}"))?;
+
+    let mut result = String::new();
+    let mut iter = content.chars();
+    while let Some(c) = iter.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match iter.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('a') => result.push('\u{7}'),
+            Some('b') => result.push('\u{8}'),
+            Some('f') => result.push('\u{C}'),
+            Some('v') => result.push('\u{B}'),
+            Some(other @ ('"' | '\\' | '\'' | '?')) => result.push(other),
+            Some(other) => return Err(format!("invalid escape sequence \\{other}")),
+            None => return Err("string ends with \\".to_string()),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Parses the content of a compiled .mo file.
+/// Keys are built the same way as for .po files.
+pub fn parse_mo(bytes: &[u8], settings: Settings) -> Result<HashMap<String, String>, String> {
+    let read_u32 = |offset: usize, big_endian: bool| -> Result<u32, String> {
+        let word: [u8; 4] = bytes
+            .get(offset..offset + 4)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| format!("file is truncated at offset {offset}"))?;
+        Ok(if big_endian {
+            u32::from_be_bytes(word)
+        } else {
+            u32::from_le_bytes(word)
+        })
+    };
+
+    let big_endian = match read_u32(0, false)? {
+        MO_MAGIC => false,
+        magic if magic.swap_bytes() == MO_MAGIC => true,
+        _ => return Err("file is not a .mo file".to_string()),
+    };
+
+    let read_usize = |offset: usize| -> Result<usize, String> {
+        usize::try_from(read_u32(offset, big_endian)?).map_err(|e| e.to_string())
+    };
+
+    let read_string = |table: usize, index: usize| -> Result<&str, String> {
+        let length = read_usize(table + index * 8)?;
+        let offset = read_usize(table + index * 8 + 4)?;
+        let string = bytes
+            .get(offset..offset + length)
+            .ok_or_else(|| format!("file is truncated at offset {offset}"))?;
+        std::str::from_utf8(string)
+            .map_err(|e| format!("string at offset {offset} is not utf-8, {e}"))
+    };
+
+    let count = read_usize(8)?;
+    let ids = read_usize(12)?;
+    let translations = read_usize(16)?;
+
+    let mut result = HashMap::new();
+    for index in 0..count {
+        let id = read_string(ids, index)?;
+        let translation = read_string(translations, index)?;
+
+        let (context, id) = match id.split_once('\u{4}') {
+            Some((context, id)) => (Some(context.to_string()), id),
+            None => (None, id),
+        };
+
+        if let Some((id, plural)) = id.split_once('\0') {
+            return Err(format!(
+                "plural entries are not supported, but msgid {id:?} has the plural {plural:?}"
+            ));
+        }
+
+        let id = id.to_string();
+        let translation = translation
+            .split('\0')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        insert_entry(context, id, translation, false, settings, &mut result)?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_po, Settings};
+
+    #[test]
+    fn plural_entries_are_rejected() {
+        let settings = Settings {
+            include_fuzzy: false,
+            untranslated_as_id: false,
+        };
+        let content = "msgid \"{0} file\"\nmsgid_plural \"{0} files\"\nmsgstr[0] \"{0} Datei\"\nmsgstr[1] \"{0} Dateien\"\n";
+        assert_eq!(
+            Err("line 2: plural entries are not supported, but msgid \"{0} file\" has the plural \"{0} files\"".to_string()),
+            parse_po(content, settings)
+        );
+    }
+}
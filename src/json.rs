@@ -0,0 +1,54 @@
+//! Reading of JSON resource files like the ones used by i18next.
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Parses the content of a JSON resource file.
+/// The root must be an object, nested objects are flattened into dotted keys.
+/// Keys with a null value are treated as not translated.
+pub fn parse(content: &str) -> Result<HashMap<String, String>, String> {
+    let root: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let Value::Object(root) = root else {
+        return Err("the root of the file must be an object".to_string());
+    };
+
+    let mut result = HashMap::new();
+    flatten("", &root, &mut result)?;
+    Ok(result)
+}
+
+/// Flattens a JSON object into `result`, prefixing all keys with `prefix`.
+fn flatten(
+    prefix: &str,
+    object: &Map<String, Value>,
+    result: &mut HashMap<String, String>,
+) -> Result<(), String> {
+    for (k, v) in object {
+        let key = if prefix.is_empty() {
+            k.clone()
+        } else {
+            format!("{prefix}.{k}")
+        };
+
+        let value = match v {
+            Value::Null => continue,
+            Value::Object(nested) => {
+                flatten(&key, nested, result)?;
+                continue;
+            }
+            Value::String(value) => value.clone(),
+            Value::Bool(value) => value.to_string(),
+            Value::Number(value) => value.to_string(),
+            Value::Array(_) => {
+                return Err(format!(
+                    "key '{key}' has an array value which is not supported"
+                ))
+            }
+        };
+
+        if result.insert(key.clone(), value).is_some() {
+            return Err(format!("key '{key}' exists more than once"));
+        }
+    }
+
+    Ok(())
+}
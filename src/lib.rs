@@ -16,34 +16,370 @@
     clippy::cargo_common_metadata,
     clippy::used_underscore_binding
 )]
+#![allow(clippy::implicit_clone)]
 use linked_hash_map::LinkedHashMap;
 use proc_macro::token_stream::IntoIter;
-use proc_macro::{TokenStream, TokenTree};
-use std::collections::{BTreeSet, HashMap, HashSet};
+use proc_macro::{Delimiter, Group, Literal, Span, TokenStream, TokenTree};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Write;
 use std::fs::File;
 use std::io::BufReader;
+use std::iter::Peekable;
 use std::mem;
 use std::path::Path;
 use unicode_xid::UnicodeXID;
 
+#[cfg(feature = "xml")]
+mod android;
+mod apple;
+#[cfg(doctest)]
+mod compile_fail;
+#[cfg(feature = "csv")]
+mod csv;
+mod derive;
+#[cfg(feature = "fluent")]
+mod fluent;
+mod gettext;
+#[cfg(feature = "json")]
+mod json;
+mod printf;
+#[cfg(feature = "toml")]
+mod toml;
+mod variables;
+#[cfg(feature = "xml")]
+mod xliff;
+#[cfg(feature = "yaml")]
+mod yaml;
+
 /// Struct that holds data associated with a single language variant.
 #[derive(Debug, Clone)]
 struct Variant {
     /// Language name
     name: String,
-    /// Path to a prop file
-    path: String,
+    /// Where the properties of this language come from
+    source: Source,
+    /// Format of the file, if it was explicitly specified in the macro invocation
+    format: Option<Format>,
     /// Fallback languages
     fallbacks: Vec<String>,
+    /// Is this a right-to-left language
+    rtl: bool,
+    /// Native name of the language
+    display_name: Option<String>,
+    /// BCP-47 language tags, the first one is the primary tag
+    tags: Vec<String>,
+    /// Name of the column in a CSV or TSV table, if it is not the language name
+    column: Option<String>,
     /// Raw properties key, value
     properties: HashMap<String, String>,
+    /// Lines of the comments directly above the keys in .properties files
+    comments: HashMap<String, Vec<String>>,
     /// Key->Vec<constant string prefix, index of format argument>
     /// If the index is `usize::MAX`, then that means it's a suffix.
     properties_split_by_format_args: HashMap<String, Vec<(String, usize)>>,
 }
 
-fn parse_path(input: &mut IntoIter) -> String {
+impl Variant {
+    /// Creates a language without fallbacks and attributes.
+    fn new(name: String, source: Source) -> Self {
+        Self {
+            name,
+            source,
+            format: None,
+            fallbacks: Vec::new(),
+            rtl: false,
+            display_name: None,
+            tags: Vec::new(),
+            column: None,
+            properties: HashMap::default(),
+            comments: HashMap::default(),
+            properties_split_by_format_args: HashMap::default(),
+        }
+    }
+}
+
+/// Where the properties of a language come from.
+#[derive(Debug, Clone)]
+enum Source {
+    /// Files whose properties are merged.
+    File(Vec<LanguageFile>),
+    /// Pseudo localization generated from the default language.
+    /// The value is the length expansion in percent.
+    Pseudo(usize),
+    /// The file is found in the `dir` option by the `pattern` option.
+    /// With the `table` option, this becomes [`Source::Table`].
+    Discovered,
+    /// The column of the language in the table of the `table` option.
+    Table,
+}
+
+/// A file of a language.
+#[derive(Debug, Clone)]
+struct LanguageFile {
+    /// Path to a prop file or a directory of prop files, quoted as it appears in the macro invocation.
+    path: String,
+    /// Prefix that is added to all keys of the file, separated by a dot.
+    prefix: Option<String>,
+}
+
+/// File format of a language file.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Format {
+    /// Java .properties file.
+    Properties,
+    /// JSON file, nested objects are flattened into dotted keys.
+    Json,
+    /// GNU gettext .po file.
+    Po,
+    /// GNU gettext compiled .mo file.
+    Mo,
+    /// Project Fluent .ftl file.
+    Fluent,
+    /// XLIFF 1.2 or 2.0 file.
+    Xliff,
+    /// TOML file, nested tables are flattened into dotted keys.
+    Toml,
+    /// YAML file, nested mappings are flattened into dotted keys.
+    Yaml,
+    /// Android strings.xml resource file.
+    Android,
+    /// Apple .strings file.
+    Apple,
+    /// CSV table that contains a column per language.
+    Csv,
+    /// TSV table that contains a column per language.
+    Tsv,
+}
+
+impl Format {
+    /// Gets the format by the name used in the `format` attribute of a language.
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "properties" => Self::Properties,
+            "json" => Self::Json,
+            "po" => Self::Po,
+            "mo" => Self::Mo,
+            "fluent" => Self::Fluent,
+            "xliff" => Self::Xliff,
+            "toml" => Self::Toml,
+            "yaml" => Self::Yaml,
+            "android" => Self::Android,
+            "strings" => Self::Apple,
+            "csv" => Self::Csv,
+            "tsv" => Self::Tsv,
+            _ => return None,
+        })
+    }
+
+    /// Gets the format by the extension of a file, all unknown extensions are treated as .properties files.
+    fn from_path(path: &Path) -> Self {
+        Self::from_extension(path).unwrap_or(Self::Properties)
+    }
+
+    /// Gets the format by the extension of a file, or nothing if the extension is unknown.
+    fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+
+        let format = match extension.as_deref() {
+            Some("properties") => Self::Properties,
+            Some("json") => Self::Json,
+            Some("po" | "pot") => Self::Po,
+            Some("mo") => Self::Mo,
+            Some("ftl") => Self::Fluent,
+            Some("xlf" | "xliff") => Self::Xliff,
+            Some("toml") => Self::Toml,
+            Some("yaml" | "yml") => Self::Yaml,
+            Some("xml") => Self::Android,
+            Some("strings") => Self::Apple,
+            Some("csv") => Self::Csv,
+            Some("tsv" | "tab") => Self::Tsv,
+            _ => return None,
+        };
+
+        Some(format)
+    }
+
+    /// Gets the format of a `.xml` file by its root element.
+    /// XLIFF files have the root element `xliff`, all other files are Android resource files.
+    fn from_xml_root(content: &str) -> Self {
+        let mut rest = content.trim_start_matches('\u{feff}');
+        // Skip the XML declaration, processing instructions, comments and the doctype
+        loop {
+            rest = rest.trim_start();
+            let (prefix, end) = if rest.starts_with("<?") {
+                ("<?", "?>")
+            } else if rest.starts_with("<!--") {
+                ("<!--", "-->")
+            } else if rest.starts_with("<!") {
+                ("<!", ">")
+            } else {
+                break;
+            };
+            rest = rest[prefix.len()..]
+                .split_once(end)
+                .map_or("", |(_, rest)| rest);
+        }
+
+        let name = rest
+            .strip_prefix('<')
+            .unwrap_or_default()
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .next()
+            .unwrap_or_default();
+        let local_name = name.rsplit(':').next().unwrap_or_default();
+        if local_name == "xliff" {
+            Self::Xliff
+        } else {
+            Self::Android
+        }
+    }
+
+    /// Gets the cargo feature that is needed to read the format if it is disabled.
+    const fn disabled_feature(self) -> Option<&'static str> {
+        let (feature, enabled) = match self {
+            Self::Json => ("json", cfg!(feature = "json")),
+            Self::Fluent => ("fluent", cfg!(feature = "fluent")),
+            Self::Xliff | Self::Android => ("xml", cfg!(feature = "xml")),
+            Self::Toml => ("toml", cfg!(feature = "toml")),
+            Self::Yaml => ("yaml", cfg!(feature = "yaml")),
+            Self::Csv | Self::Tsv => ("csv", cfg!(feature = "csv")),
+            Self::Properties | Self::Po | Self::Mo | Self::Apple => return None,
+        };
+
+        if enabled {
+            None
+        } else {
+            Some(feature)
+        }
+    }
+}
+
+/// Options of the macro invocation that apply to all languages.
+#[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct Options {
+    /// When to wrap format arguments in unicode bidi isolation characters.
+    bidi_isolate: BidiIsolate,
+    /// Use gettext entries that are marked as fuzzy.
+    include_fuzzy: bool,
+    /// Use the source text of untranslated XLIFF units in all languages instead of their fallbacks.
+    untranslated_as_source: bool,
+    /// Directory in which language files are discovered.
+    dir: Option<String>,
+    /// Pattern of the names of the discovered language files, contains `{Lang}` once.
+    pattern: Option<String>,
+    /// CSV or TSV table that contains the columns of the languages without a file.
+    table: Option<String>,
+    /// Generate nested modules for the segments of dotted keys instead of escaping the dots.
+    key_modules: bool,
+    /// How the names of the generated statics are derived from the keys.
+    naming: Naming,
+    /// What happens if several keys result in the same name.
+    collisions: Collisions,
+    /// Names of the statics of renamed keys by their key.
+    renames: HashMap<String, String>,
+    /// Span of the value of the `unused` option if unused keys are reported.
+    /// The statics of the keys get this span, rustc does not report unused items that are generated by a macro.
+    unused_keys: Option<Span>,
+    /// Visibility of the generated items.
+    visibility: Visibility,
+    /// Prefix of the names of the generated items in `snake_case`, empty if the names are not prefixed.
+    prefix: String,
+    /// Path of the `get_i18n_language` function of another invocation whose selected language is shared.
+    selection: Option<String>,
+    /// Generate the `init_i18n_language_from_env` function, which requires the standard library.
+    env_init: bool,
+}
+
+impl Options {
+    /// Name of a generated item with the prefix in the case of the name.
+    fn item_name(&self, name: &str) -> String {
+        if self.prefix.is_empty() {
+            return name.to_string();
+        }
+
+        if name.chars().all(|c| c.is_ascii_uppercase() || c == '_') {
+            return format!("{}_{name}", self.prefix.to_ascii_uppercase());
+        }
+
+        if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            let mut camel_case_prefix = String::new();
+            for segment in self.prefix.split('_') {
+                let mut chars = segment.chars();
+                if let Some(first) = chars.next() {
+                    camel_case_prefix.push(first.to_ascii_uppercase());
+                    camel_case_prefix.extend(chars);
+                }
+            }
+            return format!("{camel_case_prefix}{name}");
+        }
+
+        format!("{}_{name}", self.prefix)
+    }
+}
+
+/// When to wrap format arguments in the unicode bidi isolation characters FSI and PDI.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+enum BidiIsolate {
+    /// Format arguments are never wrapped.
+    #[default]
+    Never,
+    /// Format arguments are only wrapped if the selected language is a right-to-left language.
+    Rtl,
+    /// Format arguments are always wrapped.
+    Always,
+}
+
+/// How the names of the generated statics are derived from the keys.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+enum Naming {
+    /// Keys are used as they are, characters that are not allowed in identifiers are escaped.
+    #[default]
+    Keep,
+    /// Keys are converted to `SCREAMING_SNAKE_CASE`.
+    ScreamingSnake,
+    /// Keys are converted to `snake_case`.
+    Snake,
+}
+
+/// What happens if several keys result in the same name of a static.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+enum Collisions {
+    /// The colliding keys get a suffix with the hash of the key.
+    #[default]
+    Suffix,
+    /// Colliding keys are a compiler error.
+    Error,
+}
+
+/// Visibility of the generated items.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+enum Visibility {
+    /// The generated items are public.
+    #[default]
+    Public,
+    /// The generated items are only visible in the crate that invokes the macro.
+    Crate,
+}
+
+impl Visibility {
+    /// The visibility modifier in rust source code.
+    const fn modifier(self) -> &'static str {
+        match self {
+            Self::Public => "pub",
+            Self::Crate => "pub(crate)",
+        }
+    }
+}
+
+/// Length expansion in percent of a pseudo localization if none is specified.
+const DEFAULT_PSEUDO_EXPANSION: usize = 30;
+
+/// Parses a rust path (or any other token sequence that does not contain a group) until the next ';'.
+fn parse_path(input: &mut Peekable<IntoIter>) -> String {
     let mut language_name = String::new();
 
     for next in input.by_ref() {
@@ -69,14 +405,16 @@ fn parse_path(input: &mut IntoIter) -> String {
 }
 
 /// Makes an impl for a tuple with k elements into a format arg.
-fn output_tuple_arg_impl(output: &mut String, k: usize, prefix: &str) {
+fn output_tuple_arg_impl(output: &mut String, k: usize, prefix: &str, parameter_trait: &str) {
     output.push_str("impl<");
 
     for n in 0..k {
         _ = write!(output, "D{n}: core::fmt::Display, ");
     }
 
-    output.push_str("> I18NFormatParameter<");
+    output.push_str("> ");
+    output.push_str(parameter_trait);
+    output.push('<');
     output.push_str(k.to_string().as_str());
     output.push_str("> for ");
     output.push_str(prefix);
@@ -101,8 +439,10 @@ fn output_tuple_arg_impl(output: &mut String, k: usize, prefix: &str) {
 }
 
 /// Makes an impl for an array with k elements into a format arg.
-fn output_array_arg_impl(output: &mut String, k: usize, prefix: &str) {
-    output.push_str("impl<T: core::fmt::Display> I18NFormatParameter<");
+fn output_array_arg_impl(output: &mut String, k: usize, prefix: &str, parameter_trait: &str) {
+    output.push_str("impl<T: core::fmt::Display> ");
+    output.push_str(parameter_trait);
+    output.push('<');
     output.push_str(k.to_string().as_str());
     output.push_str("> for ");
     output.push_str(prefix);
@@ -132,9 +472,11 @@ fn output_array_arg_impl(output: &mut String, k: usize, prefix: &str) {
 ///
 #[proc_macro]
 pub fn i18n(input: TokenStream) -> TokenStream {
-    let mut token_iter: IntoIter = input.into_iter();
+    let mut token_iter: Peekable<IntoIter> = input.into_iter().peekable();
 
     let mut variants = LinkedHashMap::new();
+    let mut options = Options::default();
+    let mut default_variant = None;
 
     let mut language_name = parse_path(&mut token_iter);
 
@@ -147,386 +489,2018 @@ pub fn i18n(input: TokenStream) -> TokenStream {
         "Trying to parse language name but no language name supplied."
     );
 
-    let default_variant = parse_default_variant(&mut token_iter, &mut variants);
-
     while let Some(next) = token_iter.next() {
-        let TokenTree::Ident(lit) = next else {
-            panic!("Trying to parse language enum name, a Ident, but got non Ident TokenTree.");
+        let lit = match next {
+            TokenTree::Ident(lit) => lit,
+            TokenTree::Punct(p) if p.as_char() == '#' => {
+                parse_option(&mut token_iter, &mut options);
+                continue;
+            }
+            _ => panic!(
+                "Trying to parse language enum name, a Ident, or an option, but got something else."
+            ),
         };
 
         let variant_name = lit.to_string();
 
-        let Some(TokenTree::Punct(p)) = token_iter.next() else {
-            panic!(
-                "Trying to parse = after language enum name {language_name}, but got non Punct TokenTree"
-            );
+        let variant = match token_iter.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {
+                token_iter.next();
+                parse_variant(&mut token_iter, variant_name)
+            }
+            //Languages without a file use the file found by the dir and pattern options.
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' || p.as_char() == ';' => {
+                parse_variant_declaration(&mut token_iter, variant_name, Source::Discovered)
+            }
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+                parse_variant_declaration(&mut token_iter, variant_name, Source::Discovered)
+            }
+            _ => panic!(
+                "Trying to parse = after language enum name {language_name}, but got something else"
+            ),
         };
 
-        assert_eq!(
-            p.as_char(),
-            '=',
-            "Trying to parse = after language enum name {language_name}, but got {}",
-            p.as_char()
-        );
-
-        let Some(TokenTree::Literal(lit)) = token_iter.next() else {
-            panic!(
-                "Trying to parse language file path of language {language_name}, a literal, but got non literal."
+        if default_variant.is_none() {
+            assert!(
+                !matches!(variant.source, Source::Pseudo(_)),
+                "The default language {} must be read from a file.",
+                variant.name
             );
-        };
-
-        let variant_path = lit.to_string();
-        let mut fallbacks = Vec::new();
-
-        loop {
-            let Some(TokenTree::Punct(p)) = token_iter.next() else {
-                panic!(
-                    "Trying to parse ; after language {variant_name} file path {variant_path}, but got non Punct TokenTree"
-                );
-            };
-
-            if p.as_char() == ';' {
-                break;
-            }
-
-            assert_eq!(p.as_char(), ',', "Trying to parse ; or , after language {variant_name} file path {variant_path}, but got {}", p.as_char());
-
-            match token_iter.next() {
-                Some(TokenTree::Ident(lit)) => {
-                    fallbacks.push(lit.to_string());
-                }
-                _ => {
-                    panic!(
-                        "Trying to parse fallback language name for language {language_name}, a Ident, but got non Ident TokenTree."
-                    );
-                }
-            }
+            assert!(
+                variant.fallbacks.is_empty(),
+                "The default language {} cannot have fallbacks.",
+                variant.name
+            );
+            default_variant = Some(variant.name.clone());
         }
 
-        variants.insert(
-            variant_name.clone(),
-            Variant {
-                name: variant_name,
-                path: variant_path,
-                fallbacks,
-                properties: HashMap::default(),
-                properties_split_by_format_args: HashMap::default(),
-            },
-        );
+        variants.insert(variant.name.clone(), variant);
     }
 
-    read_property_files(&mut variants);
+    let Some(default_variant) = default_variant else {
+        panic!("Trying to parse language default enum name, a ident, but got nothing.");
+    };
+    assert!(
+        options.unused_keys.is_none() || options.visibility != Visibility::Public,
+        "Option unused = warn requires visibility = pub(crate), rustc does not report unused public items"
+    );
+
+    assign_table_columns(&options, &mut variants);
+    discover_language_files(&options, &mut variants);
+
+    read_property_files(&default_variant, &options, &mut variants);
     validate_fallbacks_exist(&variants);
     validate_all_keys_in_default_language(&default_variant, &variants);
     resolve_fallbacks_properties(&default_variant, &mut variants);
+    resolve_message_references(&mut variants);
+    resolve_named_arguments(&default_variant, &mut variants);
     parse_property_values_for_substitution_format(&mut variants);
+    generate_pseudo_localizations(&default_variant, &mut variants);
 
-    let output = generate_output(&language_name, &default_variant, &variants);
+    generate_output(&language_name, &default_variant, &variants, &options)
+}
 
-    match output.parse::<TokenStream>() {
+/// Parses generated rust source code, panics if it is invalid.
+fn parse_generated_code(code: &str) -> TokenStream {
+    match code.parse::<TokenStream>() {
         Ok(e) => e,
-        Err(r) => panic!("Generated rust source code is invalid\n {output}\n error={r}"),
+        Err(r) => panic!("Generated rust source code is invalid\n {code}\n error={r}"),
     }
 }
 
-/// Parses the default variant from the token stream and add it to the variant list.
-/// returns the name of the default variant.
-fn parse_default_variant(
-    token_iter: &mut IntoIter,
-    variants: &mut LinkedHashMap<String, Variant>,
-) -> String {
-    let Some(TokenTree::Ident(lit)) = token_iter.next() else {
-        panic!("Trying to parse language default enum name, a ident, but got non ident.");
-    };
+/// Sets the span of a token and all tokens in it.
+fn respan(mut tree: TokenTree, span: Span) -> TokenTree {
+    if let TokenTree::Group(group) = &tree {
+        let stream = group
+            .stream()
+            .into_iter()
+            .map(|inner| respan(inner, span))
+            .collect();
+        tree = TokenTree::Group(Group::new(group.delimiter(), stream));
+    }
+
+    tree.set_span(span);
+    tree
+}
 
-    let default_variant = lit.to_string();
+///
+/// Generate i18n key/value pairs from the `#[i18n(...)]` attributes of the language enum,
+/// the same items as the `i18n!` macro are generated next to the enum.
+///
+/// # Panics
+/// if the attributes are not correct or the properties files referenced cant be read.
+///
+#[proc_macro_derive(I18n, attributes(i18n))]
+pub fn derive_i18n(input: TokenStream) -> TokenStream {
+    i18n(derive::translate(input))
+}
 
-    let Some(TokenTree::Punct(p)) = token_iter.next() else {
-        panic!("Trying to parse = after default language enum name, but got non Punct TokenTree");
+/// Parses an option of the macro invocation, the `[name = value]` after the #.
+fn parse_option(token_iter: &mut Peekable<IntoIter>, options: &mut Options) {
+    let stream = match token_iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group.stream(),
+        _ => panic!("Trying to parse [option] after #, but got something else"),
     };
 
-    assert!(
-        (p.as_char() == '='),
-        "Trying to parse = after default language enum name, but got {}",
-        p.as_char()
-    );
+    let mut option_iter = stream.into_iter().peekable();
+    let Some(TokenTree::Ident(name)) = option_iter.next() else {
+        panic!("Trying to parse name of option, a Ident, but got non Ident TokenTree.");
+    };
 
-    assert!(
-        !default_variant.is_empty(),
-        "Trying to parse language default variant but got empty token tree."
-    );
+    parse_option_value_of(&name.to_string(), &mut option_iter, options);
+}
+
+/// Parses the value of the option `name` and stores it in the options.
+fn parse_option_value_of(name: &str, token_iter: &mut Peekable<IntoIter>, options: &mut Options) {
+    match name {
+        "bidi_isolate" => {
+            let value = parse_option_value(name, token_iter);
+            options.bidi_isolate = match value.as_str() {
+                "never" => BidiIsolate::Never,
+                "rtl" => BidiIsolate::Rtl,
+                "always" => BidiIsolate::Always,
+                other => panic!(
+                    "Option bidi_isolate must be one of never, rtl or always, but got {other}"
+                ),
+            };
+        }
+        "fuzzy" => {
+            options.include_fuzzy = parse_option_switch(name, token_iter, "skip", "include");
+        }
+        "untranslated" => {
+            options.untranslated_as_source =
+                parse_option_switch(name, token_iter, "fallback", "source");
+        }
+        "dotted_keys" => {
+            options.key_modules = parse_option_switch(name, token_iter, "escape", "modules");
+        }
+        "naming" => {
+            let value = parse_option_value(name, token_iter);
+            options.naming = match value.as_str() {
+                "keep" => Naming::Keep,
+                "screaming_snake" => Naming::ScreamingSnake,
+                "snake" => Naming::Snake,
+                other => panic!(
+                    "Option naming must be one of keep, screaming_snake or snake, but got {other}"
+                ),
+            };
+        }
+        "rename" => parse_rename(token_iter, options),
+        "unused" => {
+            let value = parse_option_value_token(name, token_iter);
+            options.unused_keys = match value.to_string().as_str() {
+                "ignore" => None,
+                "warn" => Some(value.span()),
+                other => panic!("Option unused must be one of ignore or warn, but got {other}"),
+            };
+        }
+        "visibility" => parse_visibility(token_iter, options),
+        "prefix" => parse_prefix(token_iter, options),
+        "selection" => parse_selection(token_iter, options),
+        "env_init" => options.env_init = parse_option_switch(name, token_iter, "false", "true"),
+        "collisions" => {
+            let value = parse_option_value(name, token_iter);
+            options.collisions = match value.as_str() {
+                "suffix" => Collisions::Suffix,
+                "error" => Collisions::Error,
+                other => {
+                    panic!("Option collisions must be one of suffix or error, but got {other}")
+                }
+            };
+        }
+        "dir" | "pattern" | "table" => {
+            let value = parse_option_value(name, token_iter);
+            let value = parse_string_literal(&value).unwrap_or_else(|| {
+                panic!("Option {name} must be a string literal, but got {value}")
+            });
+            if name == "dir" {
+                options.dir = Some(value);
+            } else if name == "table" {
+                options.table = Some(value);
+            } else {
+                assert_eq!(
+                    value.matches(LANGUAGE_PLACEHOLDER).count(),
+                    1,
+                    "Option pattern must contain {LANGUAGE_PLACEHOLDER} exactly once, but got {value}"
+                );
+                assert!(
+                    !value.contains(['/', '\\']),
+                    "Option pattern must be a file name without directories, but got {value}"
+                );
+                options.pattern = Some(value);
+            }
+        }
+        other => panic!("Unknown option {other}"),
+    }
+}
 
-    let Some(TokenTree::Literal(lit)) = token_iter.next() else {
-        panic!("Trying to parse language default file path, a literal, but got non literal.");
+/// Parses the `"key" => NAME` part of a rename.
+fn parse_rename(token_iter: &mut Peekable<IntoIter>, options: &mut Options) {
+    let key = match token_iter.next() {
+        Some(TokenTree::Literal(lit)) => parse_string_literal(&lit.to_string()),
+        _ => None,
     };
+    let Some(key) = key else {
+        panic!("Trying to parse key of rename, a string literal, but got something else");
+    };
+
+    for expected in ['=', '>'] {
+        match token_iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == expected => {}
+            _ => panic!("Trying to parse => after rename of key '{key}', but got something else"),
+        }
+    }
 
-    let Some(TokenTree::Punct(p)) = token_iter.next() else {
-        panic!("Trying to parse ; after language default file path, but got non Punct TokenTree");
+    let Some(TokenTree::Ident(name)) = token_iter.next() else {
+        panic!("Trying to parse new name of key '{key}', a Ident, but got non Ident TokenTree.");
     };
 
-    assert_eq!(
-        p.as_char(),
-        ';',
-        "Trying to parse ; after language default file path, but got {}",
-        p.as_char()
+    if let Some(next) = token_iter.next() {
+        panic!("Trying to parse end of rename of key '{key}', but got {next}");
+    }
+
+    if let Some(previous) = options.renames.insert(key.clone(), name.to_string()) {
+        panic!("Key '{key}' is renamed to both {previous} and {name}");
+    }
+}
+
+/// Parses the `= value` part of the prefix option.
+fn parse_prefix(token_iter: &mut Peekable<IntoIter>, options: &mut Options) {
+    let value = parse_option_value("prefix", token_iter);
+    assert!(
+        is_valid_variable_name(&value)
+            && !value.starts_with('_')
+            && !value.contains(|c: char| c.is_ascii_uppercase()),
+        "Option prefix must be a snake_case identifier, but got {value}"
     );
+    options.prefix = value;
+}
 
-    let default_path = lit.to_string();
+/// Parses the `= path` part of the selection option.
+fn parse_selection(token_iter: &mut Peekable<IntoIter>, options: &mut Options) {
+    match token_iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '=' => {}
+        _ => panic!("Trying to parse = after option selection, but got something else"),
+    }
+    options.selection = Some(parse_path(token_iter));
+}
 
-    variants.insert(
-        default_variant.clone(),
-        Variant {
-            name: default_variant.clone(),
-            path: default_path,
-            fallbacks: vec![],
-            properties: HashMap::default(),
-            properties_split_by_format_args: HashMap::default(),
-        },
+/// Parses the `= pub(crate)` part of the visibility option.
+fn parse_visibility(token_iter: &mut Peekable<IntoIter>, options: &mut Options) {
+    match token_iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '=' => {}
+        _ => panic!("Trying to parse = after option visibility, but got something else"),
+    }
+
+    let mut value = String::new();
+    for next in token_iter {
+        value.push_str(next.to_string().replace(' ', "").as_str());
+    }
+
+    options.visibility = match value.as_str() {
+        "pub" => Visibility::Public,
+        "pub(crate)" => Visibility::Crate,
+        other => panic!(
+            "Option visibility must be one of pub or pub(crate), but got {other}. Other visibilities are relative to the module of an item, so they would differ between the statics in nested key modules and the other items"
+        ),
+    };
+}
+
+/// Parses the `= value` part of an option that is switched off by the value `off` and on by the value `on`.
+fn parse_option_switch(
+    name: &str,
+    token_iter: &mut Peekable<IntoIter>,
+    off: &str,
+    on: &str,
+) -> bool {
+    let value = parse_option_value(name, token_iter);
+    if value == off {
+        return false;
+    }
+
+    assert!(
+        value == on,
+        "Option {name} must be one of {off} or {on}, but got {value}"
     );
+    true
+}
 
-    default_variant
+/// Parses the `= value` part of an option whose value is a single token.
+fn parse_option_value(name: &str, token_iter: &mut Peekable<IntoIter>) -> String {
+    parse_option_value_token(name, token_iter).to_string()
 }
 
-/// Read all the property files for the variants.
-fn read_property_files(variants: &mut LinkedHashMap<String, Variant>) {
-    for (_, variant) in variants {
-        let path = Path::new(&variant.path[1..variant.path.len() - 1]);
-        let mut prop_file_reader = BufReader::new(File::open(path).unwrap_or_else(|_| {
-            panic!(
-                "Failed to open file {} for language {}",
-                variant.path, variant.name
-            )
-        }));
+/// Parses the value of an option including the = before it and returns its token.
+fn parse_option_value_token(name: &str, token_iter: &mut Peekable<IntoIter>) -> TokenTree {
+    match token_iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '=' => {}
+        _ => panic!("Trying to parse = after option {name}, but got something else"),
+    }
 
-        variant.properties = match jprop::parse_utf8_to_map(&mut prop_file_reader) {
-            Ok(props) => props,
-            Err(e) => panic!("Failed to parse .properties file: {}, {}", variant.path, e),
-        }
+    let Some(value) = token_iter.next() else {
+        panic!("Trying to parse value of option {name}, but got nothing");
+    };
+
+    if let Some(next) = token_iter.next() {
+        panic!("Trying to parse end of option {name}, but got {next}");
     }
+
+    value
 }
 
-///Generates the output of the proc macro.
-fn generate_output(
-    language_name: &String,
-    default_variant: &String,
-    variants: &LinkedHashMap<String, Variant>,
-) -> String {
-    let max_format_args = find_max_format_index_per_key(variants);
-    let all_complexity = find_all_format_indices(variants);
+/// Parses a language of the macro invocation after the =, including the terminating ;.
+fn parse_variant(token_iter: &mut Peekable<IntoIter>, variant_name: String) -> Variant {
+    let source = match token_iter.next() {
+        Some(TokenTree::Literal(lit)) => {
+            Source::File(parse_language_files(token_iter, lit, &variant_name))
+        }
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "pseudo" => {
+            Source::Pseudo(parse_pseudo_expansion(token_iter, &variant_name))
+        }
+        _ => panic!(
+            "Trying to parse language file path of language {variant_name}, a literal, but got non literal."
+        ),
+    };
 
-    let mut output = String::with_capacity(0x4_00_00);
+    parse_variant_declaration(token_iter, variant_name, source)
+}
 
-    generate_boiler_plate(&mut output);
-    generate_i18n_value_struct(variants, &mut output);
-    generate_language_setter(language_name, variants, &mut output);
+/// Parses the files of a language, which are separated by +.
+/// Each file may be followed by `as "prefix"` to prefix all its keys.
+fn parse_language_files(
+    token_iter: &mut Peekable<IntoIter>,
+    first: Literal,
+    variant_name: &str,
+) -> Vec<LanguageFile> {
+    let mut files = Vec::new();
+    let mut path = first;
+    loop {
+        let mut file = LanguageFile {
+            path: path.to_string(),
+            prefix: None,
+        };
 
-    for k in all_complexity {
-        if k == 0 {
-            continue;
+        if matches!(token_iter.peek(), Some(TokenTree::Ident(ident)) if ident.to_string() == "as") {
+            token_iter.next();
+            let prefix = match token_iter.next() {
+                Some(TokenTree::Literal(lit)) => parse_string_literal(&lit.to_string()),
+                _ => None,
+            };
+            let prefix = prefix.filter(|prefix| !prefix.is_empty()).unwrap_or_else(|| {
+                panic!(
+                    "Trying to parse prefix of file {} of language {variant_name}, a non empty string literal, but got something else.",
+                    file.path
+                )
+            });
+            file.prefix = Some(prefix);
         }
 
-        output_tuple_arg_impl(&mut output, k, "");
-        output_tuple_arg_impl(&mut output, k, "&");
-        output_array_arg_impl(&mut output, k, "");
-        output_array_arg_impl(&mut output, k, "&");
+        files.push(file);
+
+        if !matches!(token_iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '+') {
+            return files;
+        }
+
+        token_iter.next();
+        path = match token_iter.next() {
+            Some(TokenTree::Literal(lit)) => lit,
+            _ => panic!(
+                "Trying to parse language file path of language {variant_name} after +, a literal, but got non literal."
+            ),
+        };
     }
+}
 
-    let keys_sorted: BTreeSet<String> = variants
-        .get(default_variant)
-        .expect("unreachable: variants.get(default_variant) is none")
-        .properties
-        .keys()
-        .cloned()
-        .collect();
+/// Parses the fallbacks and attributes of a language after its source, including the terminating ;.
+fn parse_variant_declaration(
+    token_iter: &mut Peekable<IntoIter>,
+    variant_name: String,
+    source: Source,
+) -> Variant {
+    let mut variant = Variant::new(variant_name, source);
+
+    loop {
+        match token_iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ';' => break,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => match token_iter.next() {
+                Some(TokenTree::Ident(lit)) => {
+                    assert!(
+                        !matches!(variant.source, Source::Pseudo(_)),
+                        "Pseudo language {} cannot have fallbacks.",
+                        variant.name
+                    );
+                    variant.fallbacks.push(lit.to_string());
+                }
+                _ => {
+                    panic!(
+                        "Trying to parse fallback language name for language {}, a Ident, but got non Ident TokenTree.",
+                        variant.name
+                    );
+                }
+            },
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+                parse_variant_attributes(group.stream(), &mut variant);
+            }
+            _ => panic!(
+                "Trying to parse ; , or [ after language {}, but got something else",
+                variant.name
+            ),
+        }
+    }
 
-    let var_name_mapping = get_key_to_var_name_mapping(&keys_sorted);
+    variant
+}
 
-    for k in &keys_sorted {
-        let comp = *max_format_args
-            .get(k)
-            .expect("unreachable: keys_sorted not in max_format_args");
-        let mapped = var_name_mapping
-            .get(k)
-            .expect("unreachable: var_name_mapping not found");
-        output.push_str(format!("pub static {mapped}: I18NValue<{comp}> = I18NValue(&[").as_str());
-        for (_, value) in variants {
-            let prop_val = escape_string_for_source(
-                value
-                    .properties
-                    .get(k)
-                    .expect("unreachable: keys_sorted not in Variant.properties"),
+/// Parses the comma separated attributes in `[]` of a language.
+fn parse_variant_attributes(stream: TokenStream, variant: &mut Variant) {
+    let mut attr_iter = stream.into_iter();
+    while let Some(next) = attr_iter.next() {
+        let TokenTree::Ident(attribute) = next else {
+            panic!(
+                "Trying to parse attribute of language {}, a Ident, but got non Ident TokenTree.",
+                variant.name
             );
+        };
 
-            output.push('(');
-            output.push('"');
-            output.push_str(prop_val.as_str());
-            output.push_str("\",");
-
-            let format_parts = value
-                .properties_split_by_format_args
-                .get(k)
-                .expect("unreachable: keys_sorted not in Variant.properties_split_by_format_args");
-            output.push_str("&[");
-            for (prefix, index) in format_parts {
-                let prefix = escape_string_for_source(prefix);
-                output.push_str("(\"");
-                output.push_str(prefix.as_str());
-                if *index == usize::MAX {
-                    output.push_str("\", usize::MAX), ");
-                } else {
-                    output.push_str("\", ");
-                    output.push_str(index.to_string().as_str());
-                    output.push_str("), ");
-                }
+        match attribute.to_string().as_str() {
+            "rtl" => variant.rtl = true,
+            "ltr" => variant.rtl = false,
+            "name" => {
+                variant.display_name = Some(parse_attribute_value("name", &mut attr_iter, variant));
+            }
+            "format" => {
+                let format = parse_attribute_value("format", &mut attr_iter, variant);
+                variant.format = Some(Format::from_name(&format).unwrap_or_else(|| {
+                    panic!("Language {} has unknown format '{format}'", variant.name)
+                }));
+            }
+            "tag" => {
+                let tag = parse_attribute_value("tag", &mut attr_iter, variant);
+                assert!(
+                    !tag.is_empty()
+                        && tag
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+                    "Language {} has invalid tag '{tag}'",
+                    variant.name
+                );
+                variant.tags.push(tag);
+            }
+            "column" => {
+                variant.column = Some(parse_attribute_value("column", &mut attr_iter, variant));
             }
-            output.push(']');
+            other => panic!("Language {} has unknown attribute {other}", variant.name),
+        }
 
-            output.push_str("),");
+        match attr_iter.next() {
+            None => break,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+            _ => panic!(
+                "Trying to parse , after attribute of language {}, but got something else",
+                variant.name
+            ),
         }
-        output.push_str("]);\n");
     }
-    output
 }
 
-/// Generate the boilerplate types that are always needed.
-fn generate_boiler_plate(output: &mut String) {
-    output.push_str("static SELECTION: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(0);\n");
+/// Parses the `= "value"` part of a language attribute.
+fn parse_attribute_value(attribute: &str, attr_iter: &mut IntoIter, variant: &Variant) -> String {
+    match attr_iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '=' => {}
+        _ => panic!(
+            "Trying to parse = after attribute {attribute} of language {}, but got something else",
+            variant.name
+        ),
+    }
 
-    output.push_str("pub trait I18NFormatParameter<const MAX_INDEX: usize> {\n");
-    output.push_str("fn format_parameter(&self, idx: usize, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result;\n");
-    output.push_str("}\n");
+    let Some(TokenTree::Literal(lit)) = attr_iter.next() else {
+        panic!(
+            "Trying to parse value of attribute {attribute} of language {}, a literal, but got non literal.",
+            variant.name
+        );
+    };
 
-    output.push_str("impl I18NFormatParameter<0> for () {\n");
-    output.push_str("fn format_parameter(&self, idx: usize, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
-    output.push_str("Ok(())\n");
+    parse_string_literal(&lit.to_string()).unwrap_or_else(|| {
+        panic!(
+            "Value of attribute {attribute} of language {} must be a string literal, but got {lit}",
+            variant.name
+        )
+    })
+}
+
+/// Gets the value of a rust string literal as it appears in the source code.
+/// returns none if the literal is not a string literal.
+fn parse_string_literal(literal: &str) -> Option<String> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let content = raw[hashes..].strip_prefix('"')?;
+        return Some(
+            content
+                .get(..content.len().checked_sub(hashes + 1)?)?
+                .to_string(),
+        );
+    }
+
+    let content = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::new();
+    let mut iter = content.chars();
+    while let Some(c) = iter.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match iter.next()? {
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            '0' => result.push('\0'),
+            'x' => {
+                let hex: String = iter.by_ref().take(2).collect();
+                result.push(char::from(u8::from_str_radix(&hex, 16).ok()?));
+            }
+            'u' => {
+                let mut hex = String::new();
+                for n in iter.by_ref() {
+                    match n {
+                        '{' | '_' => {}
+                        '}' => break,
+                        other => hex.push(other),
+                    }
+                }
+                result.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            }
+            '\n' => {
+                //Line continuation, skips all leading whitespace of the next line.
+                let rest = iter.as_str().trim_start();
+                iter = rest.chars();
+            }
+            other => result.push(other),
+        }
+    }
+
+    Some(result)
+}
+
+/// Parses the optional `(expansion)` of a pseudo language.
+fn parse_pseudo_expansion(token_iter: &mut Peekable<IntoIter>, variant_name: &str) -> usize {
+    let Some(TokenTree::Group(group)) = token_iter.peek() else {
+        return DEFAULT_PSEUDO_EXPANSION;
+    };
+
+    if group.delimiter() != Delimiter::Parenthesis {
+        return DEFAULT_PSEUDO_EXPANSION;
+    }
+
+    let value = group.stream().to_string();
+    token_iter.next();
+    value.trim().parse().unwrap_or_else(|_| {
+        panic!(
+            "Trying to parse length expansion in percent of pseudo language {variant_name}, but got {value}"
+        )
+    })
+}
+
+/// Start of a comment line in a .properties file that renames the static of the key below.
+const RENAME_ANNOTATION: &str = "@rename ";
+
+/// Comment line in a .properties file that deprecates the key below, it may be followed by a note.
+const DEPRECATED_ANNOTATION: &str = "@deprecated";
+
+/// Placeholder for the language name in the `pattern` option.
+const LANGUAGE_PLACEHOLDER: &str = "{Lang}";
+
+/// Finds the files of the languages in the `dir` option by the `pattern` option.
+/// Languages that are declared without a file use the file found for them,
+/// all other found files are added as new languages without fallbacks.
+fn discover_language_files(options: &Options, variants: &mut LinkedHashMap<String, Variant>) {
+    let Some(dir) = &options.dir else {
+        assert!(
+            options.pattern.is_none(),
+            "Option pattern requires the option dir"
+        );
+        if let Some(variant) = variants
+            .values()
+            .find(|variant| matches!(variant.source, Source::Discovered))
+        {
+            panic!(
+                "Language {} has no file, declare it with = \"path\" or use the dir or table option",
+                variant.name
+            );
+        }
+        return;
+    };
+
+    let pattern = options.pattern.as_deref().unwrap_or("{Lang}.properties");
+    let (prefix, suffix) = pattern.split_once(LANGUAGE_PLACEHOLDER).unwrap_or_default();
+
+    let mut file_names = std::fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("Failed to read directory {dir}, {e}"))
+        .map(|entry| entry.unwrap_or_else(|e| panic!("Failed to read directory {dir}, {e}")))
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect::<Vec<_>>();
+    file_names.sort();
+
+    let mut discovered = HashMap::new();
+    for file_name in file_names {
+        let Some(stem) = file_name
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(suffix))
+            .filter(|stem| !stem.is_empty())
+        else {
+            continue;
+        };
+
+        let name = language_name_from_file_stem(stem);
+        assert!(
+            name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && name.chars().all(|c| c.is_ascii_alphanumeric()),
+            "File {file_name} in directory {dir} cannot be mapped to a language, {name} is not a valid name"
+        );
+
+        let path = format!("\"{}\"", Path::new(dir).join(&file_name).display());
+        if let Some(previous) = discovered.insert(name.clone(), path.clone()) {
+            panic!(
+                "Files {previous} and {path} in directory {dir} are both mapped to language {name}"
+            );
+        }
+
+        match variants.get_mut(&name) {
+            Some(variant) if matches!(variant.source, Source::Discovered) => {
+                variant.source = Source::File(vec![LanguageFile { path, prefix: None }]);
+            }
+            //Languages with an explicit file or pseudo languages do not use discovered files.
+            Some(_) => {}
+            None => {
+                let source = Source::File(vec![LanguageFile { path, prefix: None }]);
+                variants.insert(name.clone(), Variant::new(name, source));
+            }
+        }
+    }
+
+    if let Some(variant) = variants
+        .values()
+        .find(|variant| matches!(variant.source, Source::Discovered))
+    {
+        panic!(
+            "No file for language {} found in directory {dir} with pattern {pattern}",
+            variant.name
+        );
+    }
+}
+
+/// Assigns the table of the `table` option to the languages that are declared without a file.
+/// They read the column with their name, or the name of their `column` attribute.
+/// All other columns are ignored.
+fn assign_table_columns(options: &Options, variants: &mut LinkedHashMap<String, Variant>) {
+    let Some(table) = &options.table else {
+        return;
+    };
+
+    assert!(
+        options.dir.is_none(),
+        "Options dir and table cannot be used together"
+    );
+    let format = Format::from_path(Path::new(table));
+    assert!(
+        matches!(format, Format::Csv | Format::Tsv),
+        "Option table must be a .csv, .tsv or .tab file, but got {table}"
+    );
+    if let Some(feature) = format.disabled_feature() {
+        panic!(
+            "Option table uses the file {table}, but the feature {feature} of trivial_i18n that reads it is disabled"
+        );
+    }
+
+    for (_, variant) in variants {
+        if matches!(variant.source, Source::Discovered) {
+            variant.source = Source::Table;
+        }
+    }
+}
+
+/// Reads the column of a language from a CSV or TSV table.
+/// `tables` contains the tables that have already been parsed by path, so every table is only parsed once.
+#[cfg(feature = "csv")]
+fn read_table_column(
+    tables: &mut HashMap<String, csv::Table>,
+    table: &str,
+    format: Format,
+    variant: &Variant,
+) -> HashMap<String, String> {
+    let parsed = tables.entry(table.to_string()).or_insert_with(|| {
+        let content = std::fs::read_to_string(table)
+            .unwrap_or_else(|e| panic!("Failed to read table {table}, {e}"));
+        let delimiter = if format == Format::Csv { b',' } else { b'\t' };
+        csv::Table::parse(&content, delimiter)
+            .unwrap_or_else(|e| panic!("Failed to parse table {table}, {e}"))
+    });
+    let column = variant.column.as_deref().unwrap_or(&variant.name);
+    parsed.column(column).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read language {} from table {table}, {e}",
+            variant.name
+        )
+    })
+}
+
+/// Converts the part of a file name that names a language to the name of the language.
+/// The parts separated by `_`, `-` or `.` are converted to upper camel case, for example
+/// `de_CH` becomes `DeCh` and `swiss-german` becomes `SwissGerman`.
+fn language_name_from_file_stem(stem: &str) -> String {
+    let mut name = String::with_capacity(stem.len());
+    for part in stem.split(['_', '-', '.']) {
+        let mut chars = part.chars();
+        let Some(first) = chars.next() else {
+            continue;
+        };
+
+        name.extend(first.to_uppercase());
+        let rest = chars.as_str();
+        if rest.chars().any(char::is_lowercase) {
+            name.push_str(rest);
+        } else {
+            name.push_str(&rest.to_lowercase());
+        }
+    }
+
+    name
+}
+
+/// Read all the property files for the variants.
+fn read_property_files(
+    default_variant: &str,
+    options: &Options,
+    variants: &mut LinkedHashMap<String, Variant>,
+) {
+    #[cfg(feature = "csv")]
+    let mut tables = HashMap::new();
+    for (_, variant) in variants {
+        #[cfg(feature = "csv")]
+        if matches!(variant.source, Source::Table) {
+            let table = options.table.as_deref().unwrap_or_default();
+            let format = Format::from_path(Path::new(table));
+            variant.properties = read_table_column(&mut tables, table, format, variant);
+            continue;
+        }
+
+        let Source::File(files) = &variant.source else {
+            continue;
+        };
+
+        let mut properties = HashMap::new();
+        let mut comments = HashMap::new();
+        let mut origins = HashMap::new();
+        for file in files {
+            for variant_path in expand_language_file(file, &variant.name) {
+                let path = &variant_path[1..variant_path.len() - 1];
+                let format = language_file_format(variant, Path::new(path), &variant_path);
+                let mut file_comments = HashMap::new();
+                let file_properties = match format {
+                    #[cfg(feature = "csv")]
+                    Format::Csv | Format::Tsv => {
+                        read_table_column(&mut tables, path, format, variant)
+                    }
+                    _ => read_language_file(
+                        default_variant,
+                        options,
+                        variant,
+                        &variant_path,
+                        format,
+                        &mut file_comments,
+                    ),
+                };
+                for (key, value) in file_properties {
+                    let (key, value) = match &file.prefix {
+                        Some(prefix) => (
+                            format!("{prefix}.{key}"),
+                            prefix_message_references(&value, prefix),
+                        ),
+                        None => (key, value),
+                    };
+
+                    if let Some(previous) = origins.insert(key.clone(), variant_path.clone()) {
+                        panic!(
+                            "Language {} has the key {key} in both {previous} and {variant_path}",
+                            variant.name
+                        );
+                    }
+                    properties.insert(key, value);
+                }
+
+                for (key, comment) in file_comments {
+                    let key = match &file.prefix {
+                        Some(prefix) => format!("{prefix}.{key}"),
+                        None => key,
+                    };
+                    comments.insert(key, comment);
+                }
+            }
+        }
+
+        variant.properties = properties;
+        variant.comments = comments;
+    }
+}
+
+/// Gets the quoted paths of the files of a language file, which is either a file or a directory of files.
+/// Only the files of a directory with a known extension are used, other files like a README are skipped.
+fn expand_language_file(file: &LanguageFile, variant_name: &str) -> Vec<String> {
+    let path = Path::new(&file.path[1..file.path.len() - 1]);
+    if !path.is_dir() {
+        return vec![file.path.clone()];
+    }
+
+    let mut paths = std::fs::read_dir(path)
+        .unwrap_or_else(|e| {
+            panic!(
+                "Failed to read directory {} for language {variant_name}, {e}",
+                file.path
+            )
+        })
+        .map(|entry| {
+            entry.unwrap_or_else(|e| {
+                panic!(
+                    "Failed to read directory {} for language {variant_name}, {e}",
+                    file.path
+                )
+            })
+        })
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && Format::from_extension(path).is_some())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| format!("\"{}\"", path.display()))
+        .collect()
+}
+
+/// Reads a single file of a language, tables are read by `read_table_column`.
+/// The comments above the keys are put into `comments` if the format has comments that belong to keys.
+fn read_language_file(
+    default_variant: &str,
+    options: &Options,
+    variant: &Variant,
+    variant_path: &str,
+    format: Format,
+    comments: &mut HashMap<String, Vec<String>>,
+) -> HashMap<String, String> {
+    let path = Path::new(&variant_path[1..variant_path.len() - 1]);
+    match format {
+        Format::Properties => read_properties_file(path, variant_path, &variant.name, comments),
+        #[cfg(feature = "json")]
+        Format::Json => {
+            let content = read_file_to_string(path, variant_path, &variant.name);
+            json::parse(&content)
+                .unwrap_or_else(|e| panic!("Failed to parse .json file: {variant_path}, {e}"))
+        }
+        Format::Po => {
+            let content = read_file_to_string(path, variant_path, &variant.name);
+            gettext::parse_po(
+                &content,
+                gettext_settings(default_variant, options, variant),
+            )
+            .unwrap_or_else(|e| panic!("Failed to parse .po file: {variant_path}, {e}"))
+        }
+        Format::Mo => {
+            let content = read_file_to_bytes(path, variant_path, &variant.name);
+            gettext::parse_mo(
+                &content,
+                gettext_settings(default_variant, options, variant),
+            )
+            .unwrap_or_else(|e| panic!("Failed to parse .mo file: {variant_path}, {e}"))
+        }
+        #[cfg(feature = "fluent")]
+        Format::Fluent => {
+            let content = read_file_to_string(path, variant_path, &variant.name);
+            fluent::parse(&content)
+                .unwrap_or_else(|e| panic!("Failed to parse .ftl file: {variant_path}, {e}"))
+        }
+        #[cfg(feature = "xml")]
+        Format::Xliff => {
+            let content = read_file_to_string(path, variant_path, &variant.name);
+            xliff::parse(
+                &content,
+                untranslated_as_source(default_variant, options, variant),
+            )
+            .unwrap_or_else(|e| panic!("Failed to parse XLIFF file: {variant_path}, {e}"))
+        }
+        #[cfg(feature = "toml")]
+        Format::Toml => {
+            let content = read_file_to_string(path, variant_path, &variant.name);
+            toml::parse(&content)
+                .unwrap_or_else(|e| panic!("Failed to parse .toml file: {variant_path}, {e}"))
+        }
+        #[cfg(feature = "yaml")]
+        Format::Yaml => {
+            let content = read_file_to_string(path, variant_path, &variant.name);
+            yaml::parse(&content)
+                .unwrap_or_else(|e| panic!("Failed to parse YAML file: {variant_path}, {e}"))
+        }
+        #[cfg(feature = "xml")]
+        Format::Android => {
+            let content = read_file_to_string(path, variant_path, &variant.name);
+            android::parse(&content).unwrap_or_else(|e| {
+                panic!("Failed to parse Android strings.xml file: {variant_path}, {e}")
+            })
+        }
+        Format::Apple => {
+            let content = read_file_to_bytes(path, variant_path, &variant.name);
+            apple::parse(&content)
+                .unwrap_or_else(|e| panic!("Failed to parse .strings file: {variant_path}, {e}"))
+        }
+        #[cfg(feature = "csv")]
+        Format::Csv | Format::Tsv => unreachable!("Tables are read by read_property_files"),
+        #[cfg(not(all(
+            feature = "csv",
+            feature = "fluent",
+            feature = "json",
+            feature = "toml",
+            feature = "xml",
+            feature = "yaml"
+        )))]
+        _ => unreachable!("Files of disabled formats are rejected above"),
+    }
+}
+
+/// Gets the format of a language file from the `format` attribute of the language,
+/// or from the extension of the file. Panics if the feature that reads the format is disabled.
+fn language_file_format(variant: &Variant, path: &Path, variant_path: &str) -> Format {
+    let format = variant
+        .format
+        .unwrap_or_else(|| match Format::from_path(path) {
+            Format::Android => {
+                Format::from_xml_root(&read_file_to_string(path, variant_path, &variant.name))
+            }
+            format => format,
+        });
+    if let Some(feature) = format.disabled_feature() {
+        panic!(
+            "Language {} uses the file {variant_path}, but the feature {feature} of trivial_i18n that reads it is disabled",
+            variant.name
+        );
+    }
+
+    format
+}
+
+/// Reads a .properties file, the comments above the keys are put into `comments`.
+fn read_properties_file(
+    path: &Path,
+    variant_path: &str,
+    variant_name: &str,
+    comments: &mut HashMap<String, Vec<String>>,
+) -> HashMap<String, String> {
+    let mut prop_file_reader = BufReader::new(File::open(path).unwrap_or_else(|_| {
+        panic!("Failed to open file {variant_path} for language {variant_name}")
+    }));
+
+    match jprop::parse_utf8_to_doc(&mut prop_file_reader) {
+        Ok(elements) => properties_from_elements(elements, comments),
+        Err(e) => panic!("Failed to parse .properties file: {variant_path}, {e}"),
+    }
+}
+
+/// Gets the properties of the elements of a .properties file.
+/// The lines of a comment directly above a key are put into `comments` without the leading `#` or `!`,
+/// a blank line ends a comment.
+fn properties_from_elements(
+    elements: Vec<jprop::Element>,
+    comments: &mut HashMap<String, Vec<String>>,
+) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+    let mut comment = Vec::new();
+    for element in elements {
+        match element {
+            jprop::Element::BlankLine => comment.clear(),
+            jprop::Element::Comment(line) => {
+                let line = line.trim_start().trim_start_matches(['#', '!']);
+                comment.push(line.trim().to_string());
+            }
+            jprop::Element::Value(key, value) => {
+                if comment.is_empty() {
+                    comments.remove(&key);
+                } else {
+                    comments.insert(key.clone(), mem::take(&mut comment));
+                }
+                properties.insert(key, value);
+            }
+        }
+    }
+
+    properties
+}
+
+/// Gets the settings for reading the gettext file of a language.
+/// Untranslated entries only use the msgid as value in the default language,
+/// all other languages use their fallbacks for them.
+fn gettext_settings(
+    default_variant: &str,
+    options: &Options,
+    variant: &Variant,
+) -> gettext::Settings {
+    gettext::Settings {
+        include_fuzzy: options.include_fuzzy,
+        untranslated_as_id: variant.name == default_variant,
+    }
+}
+
+/// Should untranslated XLIFF units of a language use their source text?
+/// The default language always uses the source text, all other languages only do so if the
+/// `untranslated` option is `source`. Otherwise, they use their fallbacks.
+#[cfg(feature = "xml")]
+fn untranslated_as_source(default_variant: &str, options: &Options, variant: &Variant) -> bool {
+    options.untranslated_as_source || variant.name == default_variant
+}
+
+/// Reads a language file to a string, panics if the file cant be read.
+fn read_file_to_string(path: &Path, variant_path: &str, variant_name: &str) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!("Failed to read file {variant_path} for language {variant_name}, {e}")
+    })
+}
+
+/// Reads a language file to bytes, panics if the file cant be read.
+fn read_file_to_bytes(path: &Path, variant_path: &str, variant_name: &str) -> Vec<u8> {
+    std::fs::read(path).unwrap_or_else(|e| {
+        panic!("Failed to read file {variant_path} for language {variant_name}, {e}")
+    })
+}
+
+///Generates the output of the proc macro.
+fn generate_output(
+    language_name: &String,
+    default_variant: &String,
+    variants: &LinkedHashMap<String, Variant>,
+    options: &Options,
+) -> TokenStream {
+    let max_format_args = find_max_format_index_per_key(variants);
+    let all_complexity = find_all_format_indices(variants);
+
+    let mut output = String::with_capacity(0x4_00_00);
+
+    generate_boiler_plate(options, &mut output);
+    generate_i18n_value_struct(variants, options, &mut output);
+    generate_language_selection(language_name, variants, options, &mut output);
+    generate_language_direction(variants, options, &mut output);
+    // The language functions of a shared selection are generated by the invocation that owns it
+    if options.selection.is_none() {
+        let language_trait = options.item_name("I18NLanguage");
+        generate_language_trait(&language_trait, options, &mut output);
+        output.push_str(format!("impl {language_trait} for {language_name} {{\n").as_str());
+        generate_language_metadata(language_name, default_variant, variants, &mut output);
+        generate_language_negotiation(&language_trait, variants, &mut output);
+        output.push_str("}\n");
+        if options.env_init {
+            generate_environment_initializer(language_name, options, &mut output);
+        }
+    } else {
+        assert!(
+            !options.env_init,
+            "Option env_init cannot be combined with selection, the invocation that owns the selection generates init_i18n_language_from_env"
+        );
+    }
+
+    let parameter_trait = options.item_name("I18NFormatParameter");
+
+    for k in all_complexity {
+        if k == 0 {
+            continue;
+        }
+
+        output_tuple_arg_impl(&mut output, k, "", &parameter_trait);
+        output_tuple_arg_impl(&mut output, k, "&", &parameter_trait);
+        output_array_arg_impl(&mut output, k, "", &parameter_trait);
+        output_array_arg_impl(&mut output, k, "&", &parameter_trait);
+    }
+
+    let keys_sorted: BTreeSet<String> = variants
+        .get(default_variant)
+        .expect("unreachable: variants.get(default_variant) is none")
+        .properties
+        .keys()
+        .cloned()
+        .collect();
+
+    let mut stream = parse_generated_code(&output);
+    if options.key_modules {
+        let renames = collect_renames(options, default_variant, variants);
+        let root = build_key_modules(&keys_sorted);
+        generate_key_module(
+            &root,
+            0,
+            options,
+            &renames,
+            variants,
+            &max_format_args,
+            &mut stream,
+        );
+        return stream;
+    }
+
+    let renames = collect_renames(options, default_variant, variants);
+    let var_name_mapping =
+        get_key_to_identifier_mapping(&keys_sorted, &renames, options.naming, options.collisions);
+
+    for k in &keys_sorted {
+        let mapped = var_name_mapping
+            .get(k)
+            .expect("unreachable: var_name_mapping not found");
+        generate_static(
+            k,
+            &options.item_name(mapped),
+            0,
+            options,
+            variants,
+            &max_format_args,
+            &mut stream,
+        );
+    }
+    stream
+}
+
+/// Gets the names of the statics of keys that are renamed, either by `rename` in the macro invocation
+/// or by a `@rename` line in the comment above the key in the default language.
+fn collect_renames(
+    options: &Options,
+    default_variant: &String,
+    variants: &LinkedHashMap<String, Variant>,
+) -> HashMap<String, String> {
+    let default = variants
+        .get(default_variant)
+        .expect("unreachable: variants.get(default_variant) is none");
+
+    let mut renames = options.renames.clone();
+    for (key, comment) in &default.comments {
+        for line in comment {
+            let Some(name) = line.strip_prefix(RENAME_ANNOTATION) else {
+                continue;
+            };
+
+            let name = name.trim();
+            if let Some(previous) = renames.insert(key.clone(), name.to_string()) {
+                assert_eq!(
+                    previous, name,
+                    "Key '{key}' is renamed to both {previous} and {name}"
+                );
+            }
+        }
+    }
+
+    for (key, name) in &renames {
+        assert!(
+            default.properties.contains_key(key),
+            "Key '{key}' is renamed to {name}, but it does not exist in the default language {default_variant}"
+        );
+        assert!(
+            is_valid_variable_name(name),
+            "Key '{key}' is renamed to {name}, which is not a valid identifier"
+        );
+    }
+
+    renames
+}
+
+/// The keys of a module, when dotted keys are generated as nested modules.
+#[derive(Debug, Default)]
+struct KeyModule<'a> {
+    /// The keys of the statics in this module by the last segment of the key.
+    statics: BTreeMap<String, &'a str>,
+    /// The nested modules by their segment of the key.
+    modules: BTreeMap<String, Self>,
+}
+
+/// Sorts the keys into nested modules by their dot separated segments.
+/// Keys with empty segments, like `a..b`, cannot be split and stay in the root module.
+fn build_key_modules(keys: &BTreeSet<String>) -> KeyModule<'_> {
+    let mut root = KeyModule::default();
+    for key in keys {
+        let segments: Vec<&str> = key.split('.').collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            root.statics.insert(key.clone(), key);
+            continue;
+        }
+
+        let (last, path) = segments
+            .split_last()
+            .expect("unreachable: split always yields a segment");
+        let mut module = &mut root;
+        for segment in path {
+            module = module.modules.entry((*segment).to_string()).or_default();
+        }
+        module.statics.insert((*last).to_string(), key);
+    }
+
+    root
+}
+
+/// Generates the statics and nested modules of a module.
+/// The segments of each module are named like keys, statics and modules do not collide
+/// because they are in different namespaces. Unless the naming strategy keeps the keys,
+/// modules are named in `snake_case`. The statics and modules of the root module get the prefix
+/// of the generated items. Modules that would shadow a crate, a primitive type or an item of the
+/// prelude get a `_` suffix.
+fn generate_key_module(
+    module: &KeyModule,
+    depth: usize,
+    options: &Options,
+    renames: &HashMap<String, String>,
+    variants: &LinkedHashMap<String, Variant>,
+    max_format_args: &HashMap<String, usize>,
+    output: &mut TokenStream,
+) {
+    let static_renames = module
+        .statics
+        .iter()
+        .filter_map(|(segment, key)| Some((segment.clone(), renames.get(*key)?.clone())))
+        .collect();
+    let static_names = get_key_to_identifier_mapping(
+        &module.statics.keys().cloned().collect::<BTreeSet<_>>(),
+        &static_renames,
+        options.naming,
+        options.collisions,
+    );
+    for (segment, key) in &module.statics {
+        let mapped = static_names
+            .get(segment)
+            .expect("unreachable: static_names not found");
+        // Only the items of the root module are prefixed, the nested modules are in their namespace
+        let mapped = if depth == 0 {
+            options.item_name(mapped)
+        } else {
+            mapped.clone()
+        };
+        generate_static(
+            key,
+            &mapped,
+            depth,
+            options,
+            variants,
+            max_format_args,
+            output,
+        );
+    }
+
+    let module_naming = if options.naming == Naming::Keep {
+        Naming::Keep
+    } else {
+        Naming::Snake
+    };
+    let module_names = get_key_to_identifier_mapping(
+        &module.modules.keys().cloned().collect::<BTreeSet<_>>(),
+        &HashMap::new(),
+        module_naming,
+        options.collisions,
+    );
+    for (segment, nested) in &module.modules {
+        let mapped = module_names
+            .get(segment)
+            .expect("unreachable: module_names not found");
+        let mapped = if depth == 0 {
+            options.item_name(mapped)
+        } else {
+            mapped.clone()
+        };
+        let mapped = if shadows_builtin_name(&mapped) {
+            let escaped = format!("{mapped}_");
+            assert!(
+                !module_names.values().any(|name| *name == escaped),
+                "Module {mapped} is escaped to {escaped}, which is also the name of another module"
+            );
+            escaped
+        } else {
+            mapped
+        };
+        let mut nested_output = TokenStream::new();
+        generate_key_module(
+            nested,
+            depth + 1,
+            options,
+            renames,
+            variants,
+            max_format_args,
+            &mut nested_output,
+        );
+        output.extend(parse_generated_code(&format!(
+            "{} mod {mapped}",
+            options.visibility.modifier()
+        )));
+        output.extend([TokenTree::Group(Group::new(
+            Delimiter::Brace,
+            nested_output,
+        ))]);
+    }
+}
+
+/// Maximum number of characters of a translation that is shown in the documentation of a static.
+const MAX_DOC_TEXT_LENGTH: usize = 80;
+
+/// Generates the documentation of the static of a key.
+/// It contains the comment above the key in the default language without annotations, the key,
+/// the number of format arguments and the truncated text of each language.
+fn generate_static_doc(
+    k: &str,
+    comp: usize,
+    variants: &LinkedHashMap<String, Variant>,
+    output: &mut String,
+) {
+    let mut lines = Vec::new();
+    let default = variants
+        .values()
+        .next()
+        .expect("unreachable: no default language");
+    if let Some(comment) = default.comments.get(k) {
+        lines.extend(
+            comment
+                .iter()
+                .filter(|line| !line.starts_with('@'))
+                .cloned(),
+        );
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+    }
+
+    lines.push(format!("Key: {}", doc_code_span(k)));
+    if comp > 0 {
+        lines.push(String::new());
+        lines.push(format!("Format arguments: {comp}"));
+    }
+    lines.push(String::new());
+
+    for (idx, variant) in variants.values().enumerate() {
+        let mut text: String = variant
+            .properties
+            .get(k)
+            .expect("unreachable: keys_sorted not in Variant.properties")
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .take(MAX_DOC_TEXT_LENGTH + 1)
+            .collect();
+        if text.chars().count() > MAX_DOC_TEXT_LENGTH {
+            text = text.chars().take(MAX_DOC_TEXT_LENGTH).collect();
+            text.push('…');
+        }
+
+        let default_marker = if idx == 0 { " (default)" } else { "" };
+        lines.push(format!(
+            "* {}{default_marker}: {}",
+            variant.name,
+            doc_code_span(&text)
+        ));
+    }
+
+    for line in lines {
+        output.push_str("#[doc = \"");
+        output.push_str(escape_string_for_source(&line).as_str());
+        output.push_str("\"]\n");
+    }
+}
+
+/// Generates the `#[deprecated]` attribute of the static of a key,
+/// if the comment above the key in the default language contains a `@deprecated` line.
+fn generate_static_deprecation(
+    k: &str,
+    variants: &LinkedHashMap<String, Variant>,
+    output: &mut String,
+) {
+    let default = variants
+        .values()
+        .next()
+        .expect("unreachable: no default language");
+    let Some(comment) = default.comments.get(k) else {
+        return;
+    };
+
+    let mut notes = comment.iter().filter_map(|line| {
+        let note = line.strip_prefix(DEPRECATED_ANNOTATION)?;
+        if note.is_empty() || note.starts_with(char::is_whitespace) {
+            return Some(note.trim());
+        }
+
+        None
+    });
+
+    let Some(note) = notes.next() else {
+        return;
+    };
+
+    assert!(
+        notes.next().is_none(),
+        "Key '{k}' is deprecated more than once"
+    );
+
+    if note.is_empty() {
+        output.push_str("#[deprecated]\n");
+        return;
+    }
+
+    output.push_str("#[deprecated(note = \"");
+    output.push_str(escape_string_for_source(note).as_str());
+    output.push_str("\")]\n");
+}
+
+/// Formats a text as a markdown code span, the text may contain backticks.
+fn doc_code_span(text: &str) -> String {
+    let mut fence = String::from("`");
+    while text.contains(fence.as_str()) {
+        fence.push('`');
+    }
+
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{fence} {text} {fence}")
+    } else {
+        format!("{fence}{text}{fence}")
+    }
+}
+
+/// Generates the static of a key. `depth` is the number of modules between the static and the generated boilerplate.
+fn generate_static(
+    k: &str,
+    mapped: &str,
+    depth: usize,
+    options: &Options,
+    variants: &LinkedHashMap<String, Variant>,
+    max_format_args: &HashMap<String, usize>,
+    output: &mut TokenStream,
+) {
+    assert!(
+        options.unused_keys.is_none() || !mapped.starts_with('_'),
+        "The static {mapped} of key '{k}' starts with '_', rustc never reports it with unused = warn. Rename the key or use another naming."
+    );
+    let comp = *max_format_args
+        .get(k)
+        .expect("unreachable: keys_sorted not in max_format_args");
+    let value_type = format!(
+        "{}{}",
+        "super::".repeat(depth),
+        options.item_name("I18NValue")
+    );
+    let mut attributes = String::new();
+    generate_static_doc(k, comp, variants, &mut attributes);
+    generate_static_deprecation(k, variants, &mut attributes);
+    if options.unused_keys.is_some() {
+        attributes.push_str("#[allow(non_upper_case_globals)]\n");
+    }
+    output.extend(parse_generated_code(&attributes));
+
+    let mut item = format!(
+        "{} static {mapped}: {value_type}<{comp}> = {value_type}(&[",
+        options.visibility.modifier()
+    );
+    for (_, value) in variants {
+        let prop_val = escape_string_for_source(
+            value
+                .properties
+                .get(k)
+                .expect("unreachable: keys_sorted not in Variant.properties"),
+        );
+
+        item.push('(');
+        item.push('"');
+        item.push_str(prop_val.as_str());
+        item.push_str("\",");
+
+        let format_parts = value
+            .properties_split_by_format_args
+            .get(k)
+            .expect("unreachable: keys_sorted not in Variant.properties_split_by_format_args");
+        item.push_str("&[");
+        for (prefix, index) in format_parts {
+            let prefix = escape_string_for_source(prefix);
+            item.push_str("(\"");
+            item.push_str(prefix.as_str());
+            if *index == usize::MAX {
+                item.push_str("\", usize::MAX), ");
+            } else {
+                item.push_str("\", ");
+                item.push_str(index.to_string().as_str());
+                item.push_str("), ");
+            }
+        }
+        item.push(']');
+
+        item.push_str("),");
+    }
+    item.push_str("]);\n");
+
+    let item = parse_generated_code(&item);
+    // rustc does not report unused items that are generated by a macro, but it reports them at the span of the option
+    match options.unused_keys {
+        Some(span) => output.extend(item.into_iter().map(|tree| respan(tree, span))),
+        None => output.extend(item),
+    }
+}
+
+/// Generate the boilerplate types that are always needed.
+fn generate_boiler_plate(options: &Options, output: &mut String) {
+    let vis = options.visibility.modifier();
+    let value = options.item_name("I18NValue");
+    let parameter_trait = options.item_name("I18NFormatParameter");
+    let selected_index = options.item_name("selected_i18n_index");
+
+    output.push_str(format!("{vis} trait {parameter_trait}<const MAX_INDEX: usize> {{\n").as_str());
+    output.push_str("fn format_parameter(&self, idx: usize, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result;\n");
+    output.push_str("}\n");
+
+    output.push_str(format!("impl {parameter_trait}<0> for () {{\n").as_str());
+    output.push_str("fn format_parameter(&self, idx: usize, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
+    output.push_str("Ok(())\n");
+    output.push_str("}\n");
+    output.push_str("}\n");
+
+    output.push_str(format!("impl<const MAX_INDEX: usize, T: core::fmt::Display> {parameter_trait}<MAX_INDEX> for &[T] {{\n").as_str());
+    output.push_str("fn format_parameter(&self, idx: usize, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
+    output.push_str("let ::core::option::Option::Some(dsp) = self.get(idx) else {\n");
+    output.push_str("return Ok(());\n");
+    output.push_str("};\n");
+    output.push_str("core::fmt::Display::fmt(dsp, f)\n");
+    output.push_str("}\n");
+    output.push_str("}\n");
+
+    output.push_str(format!("impl<const MAX_INDEX: usize> {value}<MAX_INDEX> {{\n").as_str());
+    output.push_str(format!("{vis} fn as_str(&self) -> &'static str {{\n").as_str());
+    output.push_str(format!("self.0[{selected_index}()].0\n").as_str());
+    output.push_str("}\n");
+    output.push_str(format!("{vis} const fn default_str(&self) -> &'static str {{\n").as_str());
+    output.push_str("self.0[0].0\n");
+    output.push_str("}\n");
+
+    output.push_str(format!("{vis} fn format_with(&self, arg: impl {parameter_trait}<MAX_INDEX>, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {{\n").as_str());
+    output
+        .push_str(format!("for (prefix, arg_index) in self.0[{selected_index}()].1 {{\n").as_str());
+    output.push_str("let idx = *arg_index;\n");
+    output.push_str("f.write_str(prefix)?;\n");
+    generate_format_parameter_call(options, "arg", output);
+    output.push_str("}\n");
+    output.push_str("Ok(())\n");
+    output.push_str("}\n");
+
+    output.push_str(format!("{vis} fn format_into<T: core::fmt::Write>(&self, arg: impl {parameter_trait}<MAX_INDEX>, f: &mut T) -> core::fmt::Result {{\n").as_str());
+    output.push_str(
+        format!("struct FMT<'a, const M: usize, T: {parameter_trait}<M>>(&'a {value}<M>, T);\n")
+            .as_str(),
+    );
+    output.push_str(format!("impl<const M: usize, T: {parameter_trait}<M>> core::fmt::Display for FMT<'_, M, T> {{\n").as_str());
+    output.push_str("fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
+    output.push_str(
+        format!("for (prefix, arg_index) in self.0.0[{selected_index}()].1 {{\n").as_str(),
+    );
+    output.push_str("let idx = *arg_index;\n");
+    output.push_str("f.write_str(prefix)?;\n");
+    generate_format_parameter_call(options, "self.1", output);
+    output.push_str("}\n");
+    output.push_str("Ok(())\n");
+    output.push_str("}\n");
+    output.push_str("}\n");
+    output.push_str("let formatter = FMT(self, arg);\n");
+    output.push_str("core::write!(f, \"{}\", formatter)\n");
+    output.push_str("}\n");
+
+    output.push_str(
+        format!("{vis} fn format(&self, arg: impl {parameter_trait}<MAX_INDEX>) -> String {{\n")
+            .as_str(),
+    );
+    output.push_str(
+        format!("struct FMT<'a, const M: usize, T: {parameter_trait}<M>>(&'a {value}<M>, T);\n")
+            .as_str(),
+    );
+    output.push_str(format!("impl<const M: usize, T: {parameter_trait}<M>> core::fmt::Display for FMT<'_, M, T> {{\n").as_str());
+    output.push_str("fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
+    output.push_str(
+        format!("for (prefix, arg_index) in self.0.0[{selected_index}()].1 {{\n").as_str(),
+    );
+    output.push_str("let idx = *arg_index;\n");
+    output.push_str("f.write_str(prefix)?;\n");
+    generate_format_parameter_call(options, "self.1", output);
+    output.push_str("}\n");
+    output.push_str("Ok(())\n");
+    output.push_str("}\n");
+    output.push_str("}\n");
+    output.push_str("let formatter = FMT(self, arg);\n");
+    output.push_str("ToString::to_string(&formatter)\n");
+    output.push_str("}\n");
+
+    output.push_str("}\n");
+
+    generate_i18n_value_conversions(&value, output);
+}
+
+/// Generates the conversions of the `I18NValue` to strings.
+fn generate_i18n_value_conversions(value: &str, output: &mut String) {
+    output.push_str(
+        format!("impl<const MAX_INDEX: usize> AsRef<str> for {value}<MAX_INDEX> {{\n").as_str(),
+    );
+    output.push_str("fn as_ref(&self) -> &str {\n");
+    output.push_str("self.as_str()\n");
+    output.push_str("}\n");
+    output.push_str("}\n");
+
+    output.push_str(
+        format!("impl<const MAX_INDEX: usize> From<{value}<MAX_INDEX>> for String {{\n").as_str(),
+    );
+    output.push_str(format!("fn from(value: {value}<MAX_INDEX>) -> String {{\n").as_str());
+    output.push_str("value.as_str().to_string()\n");
+    output.push_str("}\n");
+    output.push_str("}\n");
+
+    output.push_str(
+        format!("impl<const MAX_INDEX: usize> From<{value}<MAX_INDEX>> for &'static str {{\n")
+            .as_str(),
+    );
+    output.push_str(format!("fn from(value: {value}<MAX_INDEX>) -> &'static str {{\n").as_str());
+    output.push_str("value.as_str()\n");
+    output.push_str("}\n");
+    output.push_str("}\n");
+
+    output.push_str(
+        format!("impl<const MAX_INDEX: usize> core::fmt::Display for {value}<MAX_INDEX> {{\n")
+            .as_str(),
+    );
+    output.push_str(" fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
+    output.push_str("f.write_str(self.as_str())\n");
+    output.push_str("}\n");
+    output.push_str("}\n");
+}
+
+/// Generates the call to `format_parameter` for the format argument `idx` in the format functions.
+fn generate_format_parameter_call(options: &Options, arg: &str, output: &mut String) {
+    output.push_str("if idx != usize::MAX {\n");
+    match options.bidi_isolate {
+        BidiIsolate::Never => {
+            output.push_str(format!("{arg}.format_parameter(idx, f)?;\n").as_str());
+        }
+        BidiIsolate::Rtl => {
+            output.push_str(
+                format!("if {}() {{\n", options.item_name("is_i18n_language_rtl")).as_str(),
+            );
+            output.push_str("f.write_str(\"\\u{2068}\")?;\n");
+            output.push_str(format!("{arg}.format_parameter(idx, f)?;\n").as_str());
+            output.push_str("f.write_str(\"\\u{2069}\")?;\n");
+            output.push_str("} else {\n");
+            output.push_str(format!("{arg}.format_parameter(idx, f)?;\n").as_str());
+            output.push_str("}\n");
+        }
+        BidiIsolate::Always => {
+            output.push_str("f.write_str(\"\\u{2068}\")?;\n");
+            output.push_str(format!("{arg}.format_parameter(idx, f)?;\n").as_str());
+            output.push_str("f.write_str(\"\\u{2069}\")?;\n");
+        }
+    }
+    output.push_str("}\n");
+}
+
+/// Generates the `I18NValue` based on the number of variants.
+fn generate_i18n_value_struct(
+    variants: &LinkedHashMap<String, Variant>,
+    options: &Options,
+    output: &mut String,
+) {
+    let vis = options.visibility.modifier();
+    output.push_str("#[derive(Debug, Copy, Clone)]\n");
+    output.push_str(
+        format!(
+            "{vis} struct {}<const MAX_INDEX: usize>(&'static [(&'static str, &'static [(&'static str, usize)]); {}]);\n",
+            options.item_name("I18NValue"),
+            variants.len()
+        )
+            .as_str(),
+    );
+}
+
+/// Generates the selection of the language, which is the `SELECTION` static with the `set_i18n_language`
+/// and `get_i18n_language` functions, or the mapping of a shared selection to the languages of this invocation.
+fn generate_language_selection(
+    language_name: &String,
+    variants: &LinkedHashMap<String, Variant>,
+    options: &Options,
+    output: &mut String,
+) {
+    let vis = options.visibility.modifier();
+    let selection = options.item_name("SELECTION");
+    let selected_index = options.item_name("selected_i18n_index");
+
+    output.push_str(format!("fn {selected_index}() -> usize {{\n").as_str());
+    if let Some(shared) = &options.selection {
+        output.push_str(format!("match {shared}() {{\n").as_str());
+        for (idx, key) in variants.keys().enumerate() {
+            output.push_str(format!("{language_name}::{key} => {idx},\n").as_str());
+        }
+        output.push_str("_ => 0,\n");
+        output.push_str("}\n");
+        output.push_str("}\n");
+        return;
+    }
+
+    output.push_str(
+        format!("{selection}.load(core::sync::atomic::Ordering::Relaxed) as usize\n").as_str(),
+    );
+    output.push_str("}\n");
+
+    output.push_str(format!("static {selection}: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(0);\n").as_str());
+
+    output.push_str(
+        format!(
+            "{vis} fn {}(language: {language_name}) {{\n",
+            options.item_name("set_i18n_language")
+        )
+        .as_str(),
+    );
+    output.push_str(format!("{selection}.store(match language {{\n").as_str());
+    for (idx, key) in variants.keys().enumerate() {
+        output.push_str(format!("{language_name}::{key} => {idx},\n").as_str());
+    }
+    output.push_str("_ => 0,\n");
+    output.push_str("} as u32, core::sync::atomic::Ordering::Relaxed);\n");
+    output.push_str("}\n");
+
+    output.push_str(
+        format!(
+            "{vis} fn {}() -> {language_name} {{\n",
+            options.item_name("get_i18n_language")
+        )
+        .as_str(),
+    );
+    output.push_str(format!("match {selected_index}() {{\n").as_str());
+    for (idx, key) in variants.keys().enumerate().skip(1) {
+        output.push_str(format!("{idx} => {language_name}::{key},\n").as_str());
+    }
+    let default_key = variants
+        .keys()
+        .next()
+        .expect("unreachable: no default language");
+    output.push_str(format!("_ => {language_name}::{default_key},\n").as_str());
+    output.push_str("}\n");
+    output.push_str("}\n");
+}
+
+/// Generates the `is_i18n_language_rtl` function.
+fn generate_language_direction(
+    variants: &LinkedHashMap<String, Variant>,
+    options: &Options,
+    output: &mut String,
+) {
+    let vis = options.visibility.modifier();
+    let rtl_indices: Vec<String> = variants
+        .values()
+        .enumerate()
+        .filter(|(_, variant)| variant.rtl)
+        .map(|(idx, _)| idx.to_string())
+        .collect();
+
+    output.push_str(
+        format!(
+            "{vis} fn {}() -> bool {{\n",
+            options.item_name("is_i18n_language_rtl")
+        )
+        .as_str(),
+    );
+    if rtl_indices.is_empty() {
+        output.push_str("false\n");
+    } else {
+        output.push_str(
+            format!("matches!({}(), ", options.item_name("selected_i18n_index")).as_str(),
+        );
+        output.push_str(rtl_indices.join(" | ").as_str());
+        output.push_str(")\n");
+    }
+    output.push_str("}\n");
+}
+
+/// Generates the trait that is implemented for the language enum.
+/// It is a trait and not an inherent impl, because the language enum can be defined in another crate.
+fn generate_language_trait(language_trait: &str, options: &Options, output: &mut String) {
+    let vis = options.visibility.modifier();
+    output.push_str(format!("{vis} trait {language_trait}: Sized {{\n").as_str());
+    output.push_str("fn display_name(&self) -> &'static str;\n");
+    output.push_str("fn language_tag(&self) -> Option<&'static str>;\n");
+    output.push_str("fn is_rtl(&self) -> bool;\n");
+    output.push_str("fn from_locale_tag(tag: &str) -> Option<Self>;\n");
+    output.push_str("fn negotiate(accept_language: &str) -> Option<Self>;\n");
+    output.push_str("fn from_env_values(lc_all: Option<&str>, lc_messages: Option<&str>, lang: Option<&str>, language: Option<&str>) -> Option<Self>;\n");
+    output.push_str("}\n");
+}
+
+/// Generates the metadata accessor methods of the impl of the language trait.
+fn generate_language_metadata(
+    language_name: &String,
+    default_variant: &String,
+    variants: &LinkedHashMap<String, Variant>,
+    output: &mut String,
+) {
+    let default_variant_value = variants
+        .get(default_variant)
+        .expect("unreachable: generate_language_metadata -> variants.get default_variant is none");
+
+    let display_name = |variant: &Variant| {
+        escape_string_for_source(variant.display_name.as_ref().unwrap_or(&variant.name))
+    };
+
+    let tag = |variant: &Variant| {
+        variant.tags.first().map_or_else(
+            || "::core::option::Option::None".to_string(),
+            |tag| {
+                format!(
+                    "::core::option::Option::Some(\"{}\")",
+                    escape_string_for_source(tag)
+                )
+            },
+        )
+    };
+
+    output.push_str("fn display_name(&self) -> &'static str {\n");
+    output.push_str("match self {\n");
+    for (key, variant) in variants {
+        output.push_str(
+            format!("{language_name}::{key} => \"{}\",\n", display_name(variant)).as_str(),
+        );
+    }
+    output.push_str(format!("_ => \"{}\",\n", display_name(default_variant_value)).as_str());
+    output.push_str("}\n");
+    output.push_str("}\n");
+
+    output.push_str("fn language_tag(&self) -> Option<&'static str> {\n");
+    output.push_str("match self {\n");
+    for (key, variant) in variants {
+        output.push_str(format!("{language_name}::{key} => {},\n", tag(variant)).as_str());
+    }
+    output.push_str(format!("_ => {},\n", tag(default_variant_value)).as_str());
     output.push_str("}\n");
     output.push_str("}\n");
 
-    output.push_str("impl<const MAX_INDEX: usize, T: core::fmt::Display> I18NFormatParameter<MAX_INDEX> for &[T] {\n");
-    output.push_str("fn format_parameter(&self, idx: usize, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
-    output.push_str("let Some(dsp) = self.get(idx) else {\n");
-    output.push_str("return Ok(());\n");
-    output.push_str("};\n");
-    output.push_str("core::fmt::Display::fmt(dsp, f)\n");
+    output.push_str("fn is_rtl(&self) -> bool {\n");
+    output.push_str("match self {\n");
+    for (key, variant) in variants {
+        output.push_str(format!("{language_name}::{key} => {},\n", variant.rtl).as_str());
+    }
+    output.push_str(format!("_ => {},\n", default_variant_value.rtl).as_str());
     output.push_str("}\n");
     output.push_str("}\n");
+}
 
-    output.push_str("impl<const MAX_INDEX: usize> I18NValue<MAX_INDEX> {\n");
-    output.push_str("pub fn as_str(&self) -> &'static str {\n");
-    output.push_str("self.0[SELECTION.load(core::sync::atomic::Ordering::Relaxed) as usize].0\n");
-    output.push_str("}\n");
-    output.push_str("pub const fn default_str(&self) -> &'static str {\n");
-    output.push_str("self.0[0].0\n");
+/// Generates the `from_locale_tag`, `negotiate` and `from_env_values` methods of the impl of the language trait.
+fn generate_language_negotiation(
+    language_trait: &str,
+    variants: &LinkedHashMap<String, Variant>,
+    output: &mut String,
+) {
+    output.push_str("fn from_locale_tag(tag: &str) -> Option<Self> {\n");
+    if variants.values().all(|variant| variant.tags.is_empty()) {
+        output.push_str("_ = tag;\n");
+    } else {
+        output.push_str("fn same_tag(a: &str, b: &str) -> bool {\n");
+        output.push_str("fn normalize(c: u8) -> u8 {\n");
+        output.push_str("if c == b'_' { b'-' } else { c.to_ascii_lowercase() }\n");
+        output.push_str("}\n");
+        output.push_str("a.len() == b.len() && a.bytes().zip(b.bytes()).all(|(a, b)| normalize(a) == normalize(b))\n");
+        output.push_str("}\n");
+        output.push_str("let mut tag = tag.trim();\n");
+        output.push_str("if let ::core::option::Option::Some(end) = tag.find(|c: char| c == '.' || c == '@') {\n");
+        output.push_str("tag = &tag[..end];\n");
+        output.push_str("}\n");
+        output.push_str("loop {\n");
+        for (key, variant) in variants {
+            for tag in &variant.tags {
+                output.push_str(format!("if same_tag(tag, \"{tag}\") {{\n").as_str());
+                output.push_str(
+                    format!("return ::core::option::Option::Some(Self::{key});\n").as_str(),
+                );
+                output.push_str("}\n");
+            }
+        }
+        output.push_str("let ::core::option::Option::Some(end) = tag.rfind(|c: char| c == '-' || c == '_') else {\n");
+        output.push_str("break;\n");
+        output.push_str("};\n");
+        output.push_str("tag = &tag[..end];\n");
+        output.push_str("if let ::core::option::Option::Some(end) = tag.rfind(|c: char| c == '-' || c == '_') {\n");
+        output.push_str("if tag.len() - end == 2 {\n");
+        output.push_str("tag = &tag[..end];\n");
+        output.push_str("}\n");
+        output.push_str("}\n");
+        output.push_str("}\n");
+        for (primary, key) in find_preferred_language_per_primary_tag(variants) {
+            output.push_str(format!("if same_tag(tag, \"{primary}\") {{\n").as_str());
+            output
+                .push_str(format!("return ::core::option::Option::Some(Self::{key});\n").as_str());
+            output.push_str("}\n");
+        }
+    }
+    output.push_str("::core::option::Option::None\n");
     output.push_str("}\n");
 
-    output.push_str("pub fn format_with(&self, arg: impl I18NFormatParameter<MAX_INDEX>, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
-    output.push_str("for (prefix, arg_index) in self.0[SELECTION.load(core::sync::atomic::Ordering::Relaxed) as usize].1 {\n");
-    output.push_str("let idx = *arg_index;\n");
-    output.push_str("f.write_str(prefix)?;\n");
-    output.push_str("if idx != usize::MAX {\n");
-    output.push_str("arg.format_parameter(idx, f)?;\n");
+    output.push_str("fn negotiate(accept_language: &str) -> Option<Self> {\n");
+    output.push_str("let mut best = ::core::option::Option::None;\n");
+    output.push_str("let mut best_quality = 0f32;\n");
+    output.push_str("for entry in accept_language.split(',') {\n");
+    output.push_str("let mut params = entry.split(';');\n");
+    output.push_str("let tag = params.next().unwrap_or_default();\n");
+    output.push_str("let mut quality = 1f32;\n");
+    output.push_str("for param in params {\n");
+    output.push_str("let param = param.trim();\n");
+    output.push_str("if let ::core::option::Option::Some(value) = param.strip_prefix(\"q=\").or_else(|| param.strip_prefix(\"Q=\")) {\n");
+    output.push_str("quality = value.trim().parse().unwrap_or(0f32);\n");
     output.push_str("}\n");
     output.push_str("}\n");
-    output.push_str("Ok(())\n");
+    output.push_str("if quality <= best_quality {\n");
+    output.push_str("continue;\n");
     output.push_str("}\n");
-
-    output.push_str("pub fn format_into<T: core::fmt::Write>(&self, arg: impl I18NFormatParameter<MAX_INDEX>, f: &mut T) -> core::fmt::Result {\n");
-    output.push_str(
-        "struct FMT<'a, const M: usize, T: I18NFormatParameter<M>>(&'a I18NValue<M>, T);\n",
-    );
     output.push_str(
-        "impl<const M: usize, T: I18NFormatParameter<M>> core::fmt::Display for FMT<'_, M, T> {\n",
+        format!("if let ::core::option::Option::Some(language) = <Self as {language_trait}>::from_locale_tag(tag) {{\n")
+            .as_str(),
     );
-    output.push_str("fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
-    output.push_str("for (prefix, arg_index) in self.0.0[SELECTION.load(core::sync::atomic::Ordering::Relaxed) as usize].1 {\n");
-    output.push_str("let idx = *arg_index;\n");
-    output.push_str("f.write_str(prefix)?;\n");
-    output.push_str("if idx != usize::MAX {\n");
-    output.push_str("self.1.format_parameter(idx, f)?;\n");
+    output.push_str("best = ::core::option::Option::Some(language);\n");
+    output.push_str("best_quality = quality;\n");
     output.push_str("}\n");
     output.push_str("}\n");
-    output.push_str("Ok(())\n");
-    output.push_str("}\n");
-    output.push_str("}\n");
-    output.push_str("let formatter = FMT(self, arg);\n");
-    output.push_str("core::write!(f, \"{}\", formatter)\n");
+    output.push_str("best\n");
     output.push_str("}\n");
 
-    output.push_str("pub fn format(&self, arg: impl I18NFormatParameter<MAX_INDEX>) -> String {\n");
+    output.push_str("fn from_env_values(lc_all: Option<&str>, lc_messages: Option<&str>, lang: Option<&str>, language: Option<&str>) -> Option<Self> {\n");
+    output.push_str("let locale = [lc_all, lc_messages, lang].into_iter().flatten().find(|value| !value.is_empty())?;\n");
     output.push_str(
-        "struct FMT<'a, const M: usize, T: I18NFormatParameter<M>>(&'a I18NValue<M>, T);\n",
+        "let base = locale.split(|c: char| c == '.' || c == '@').next().unwrap_or_default();\n",
     );
+    output.push_str("if base == \"C\" || base == \"POSIX\" {\n");
+    output.push_str("return ::core::option::Option::None;\n");
+    output.push_str("}\n");
+    output.push_str("for entry in language.unwrap_or_default().split(':') {\n");
     output.push_str(
-        "impl<const M: usize, T: I18NFormatParameter<M>> core::fmt::Display for FMT<'_, M, T> {\n",
+        format!("if let ::core::option::Option::Some(language) = <Self as {language_trait}>::from_locale_tag(entry) {{\n")
+            .as_str(),
     );
-    output.push_str("fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
-    output.push_str("for (prefix, arg_index) in self.0.0[SELECTION.load(core::sync::atomic::Ordering::Relaxed) as usize].1 {\n");
-    output.push_str("let idx = *arg_index;\n");
-    output.push_str("f.write_str(prefix)?;\n");
-    output.push_str("if idx != usize::MAX {\n");
-    output.push_str("self.1.format_parameter(idx, f)?;\n");
-    output.push_str("}\n");
-    output.push_str("}\n");
-    output.push_str("Ok(())\n");
-    output.push_str("}\n");
-    output.push_str("}\n");
-    output.push_str("let formatter = FMT(self, arg);\n");
-    output.push_str("ToString::to_string(&formatter)\n");
-    output.push_str("}\n");
-
+    output.push_str("return ::core::option::Option::Some(language);\n");
     output.push_str("}\n");
-
-    output.push_str("impl<const MAX_INDEX: usize> AsRef<str> for I18NValue<MAX_INDEX> {\n");
-    output.push_str("fn as_ref(&self) -> &str {\n");
-    output.push_str("self.as_str()\n");
-    output.push_str("}\n");
-    output.push_str("}\n");
-
-    output.push_str("impl<const MAX_INDEX: usize> From<I18NValue<MAX_INDEX>> for String {\n");
-    output.push_str("fn from(value: I18NValue<MAX_INDEX>) -> String {\n");
-    output.push_str("value.as_str().to_string()\n");
-    output.push_str("}\n");
-    output.push_str("}\n");
-
-    output.push_str("impl<const MAX_INDEX: usize> From<I18NValue<MAX_INDEX>> for &'static str {\n");
-    output.push_str("fn from(value: I18NValue<MAX_INDEX>) -> &'static str {\n");
-    output.push_str("value.as_str()\n");
-    output.push_str("}\n");
-    output.push_str("}\n");
-
-    output.push_str("impl<const MAX_INDEX: usize> core::fmt::Display for I18NValue<MAX_INDEX> {\n");
-    output.push_str(" fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
-    output.push_str("f.write_str(self.as_str())\n");
     output.push_str("}\n");
+    output.push_str(format!("<Self as {language_trait}>::from_locale_tag(locale)\n").as_str());
     output.push_str("}\n");
 }
 
-/// Generates the `I18NValue` based on the number of variants.
-fn generate_i18n_value_struct(variants: &LinkedHashMap<String, Variant>, output: &mut String) {
-    output.push_str("#[derive(Debug, Copy, Clone)]\n");
+/// Generates the `init_i18n_language_from_env` function.
+fn generate_environment_initializer(
+    language_name: &String,
+    options: &Options,
+    output: &mut String,
+) {
+    let vis = options.visibility.modifier();
     output.push_str(
         format!(
-            "pub struct I18NValue<const MAX_INDEX: usize>(&'static [(&'static str, &'static [(&'static str, usize)]); {}]);\n",
-            variants.len()
+            "{vis} fn {}() -> bool {{\n",
+            options.item_name("init_i18n_language_from_env")
         )
-            .as_str(),
+        .as_str(),
     );
+    output.push_str("let lc_all = ::std::env::var(\"LC_ALL\").ok();\n");
+    output.push_str("let lc_messages = ::std::env::var(\"LC_MESSAGES\").ok();\n");
+    output.push_str("let lang = ::std::env::var(\"LANG\").ok();\n");
+    output.push_str("let language = ::std::env::var(\"LANGUAGE\").ok();\n");
+    output.push_str(format!("let ::core::option::Option::Some(language) = <{language_name} as {}>::from_env_values(lc_all.as_deref(), lc_messages.as_deref(), lang.as_deref(), language.as_deref()) else {{\n", options.item_name("I18NLanguage")).as_str());
+    output.push_str("return false;\n");
+    output.push_str("};\n");
+    output.push_str(format!("{}(language);\n", options.item_name("set_i18n_language")).as_str());
+    output.push_str("true\n");
+    output.push_str("}\n");
 }
 
-/// Generates the `set_i18n_language` function.
-fn generate_language_setter(
-    language_name: &String,
+/// Finds the language that should be chosen if only the primary subtag (for example 'de' of 'de-CH') of a requested tag matches.
+/// If multiple languages have tags with the same primary subtag,
+/// then the language that does not fall back to any of the others is preferred.
+fn find_preferred_language_per_primary_tag(
     variants: &LinkedHashMap<String, Variant>,
-    output: &mut String,
-) {
-    output.push_str(format!("pub fn set_i18n_language(language: {language_name}) {{\n").as_str());
-    output.push_str("SELECTION.store(match language {\n");
-    for (idx, key) in variants.keys().enumerate() {
-        output.push_str(format!("{language_name}::{key} => {idx},\n").as_str());
+) -> LinkedHashMap<String, String> {
+    let mut candidates: LinkedHashMap<String, Vec<&Variant>> = LinkedHashMap::new();
+    for variant in variants.values() {
+        for tag in &variant.tags {
+            let primary = tag
+                .split(['-', '_'])
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let entry = candidates.entry(primary).or_default();
+            if !entry.iter().any(|candidate| candidate.name == variant.name) {
+                entry.push(variant);
+            }
+        }
     }
-    output.push_str("_ => 0,\n");
-    output.push_str("} as u32, core::sync::atomic::Ordering::Relaxed);\n");
-    output.push_str("}\n");
+
+    let mut result = LinkedHashMap::new();
+    for (primary, group) in candidates {
+        let preferred = group
+            .iter()
+            .find(|variant| {
+                !variant
+                    .fallbacks
+                    .iter()
+                    .any(|fallback| group.iter().any(|other| &other.name == fallback))
+            })
+            .unwrap_or(&group[0]);
+
+        result.insert(primary, preferred.name.clone());
+    }
+
+    result
 }
 
 /// Escapes some characters that cant be in a rust string without escaping.
@@ -629,6 +2603,133 @@ fn resolve_fallbacks_properties(
     }
 }
 
+/// Prefixes the `${KEY}` references of a value of a file that is read with `as "prefix"`,
+/// so they refer to the keys of the same file like they did before the keys were prefixed.
+fn prefix_message_references(value: &str, prefix: &str) -> String {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start + 2]);
+        rest = &rest[start + 2..];
+        if result.ends_with("$${") || !rest.contains('}') {
+            continue;
+        }
+
+        result.push_str(prefix);
+        result.push('.');
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Resolves all `${KEY}` references in the property values of every language.
+/// References are resolved against the values of the same language after fallbacks have been applied.
+/// `$${` can be used to write a literal `${`.
+fn resolve_message_references(variants: &mut LinkedHashMap<String, Variant>) {
+    for (_, variant) in variants.iter_mut() {
+        let mut resolved = HashMap::new();
+        let mut stack = Vec::new();
+        for k in variant.properties.keys() {
+            resolve_message_reference(variant, k, &mut resolved, &mut stack);
+        }
+
+        variant.properties = resolved;
+    }
+}
+
+/// Resolves the references of a single key, recursively resolving all keys it references.
+/// `stack` contains the keys that are currently being resolved and is used to detect cycles.
+fn resolve_message_reference(
+    variant: &Variant,
+    key: &str,
+    resolved: &mut HashMap<String, String>,
+    stack: &mut Vec<String>,
+) -> String {
+    if let Some(value) = resolved.get(key) {
+        return value.clone();
+    }
+
+    if let Some(pos) = stack.iter().position(|k| k == key) {
+        let mut cycle = stack[pos..].join(" -> ");
+        cycle.push_str(" -> ");
+        cycle.push_str(key);
+        panic!(
+            "Language '{}' has a cycle in its message references: {cycle}",
+            variant.name
+        );
+    }
+
+    let value = variant
+        .properties
+        .get(key)
+        .expect("unreachable: resolve_message_reference -> variant.properties.get is None");
+
+    stack.push(key.to_string());
+
+    let mut result = String::new();
+    let mut rest = value.as_str();
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            result.push_str(&rest[..start - 1]);
+            result.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+
+        let Some(end) = rest.find('}') else {
+            panic!(
+                "Language '{}' key '{key}' contains an unterminated message reference. Use '$${{' to write a literal '${{'.",
+                variant.name
+            );
+        };
+
+        let referenced = &rest[..end];
+        assert!(
+            variant.properties.contains_key(referenced),
+            "Language '{}' key '{key}' references key '{referenced}' which does not exist.",
+            variant.name
+        );
+
+        result.push_str(resolve_message_reference(variant, referenced, resolved, stack).as_str());
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+
+    stack.pop();
+    resolved.insert(key.to_string(), result.clone());
+    result
+}
+
+/// Replaces the named variables of values with format arguments.
+/// The index of the format argument of a variable is the order of its first appearance in the default language.
+fn resolve_named_arguments(default_variant: &str, variants: &mut LinkedHashMap<String, Variant>) {
+    let default_variant_value = variants
+        .get(default_variant)
+        .expect("unreachable: resolve_named_arguments -> variants.get default_variant is none")
+        .clone();
+
+    for (_, variant) in variants.iter_mut() {
+        for (k, v) in &mut variant.properties {
+            let names = variables::variable_names(
+                default_variant_value
+                    .properties
+                    .get(k)
+                    .expect("unreachable: resolve_named_arguments -> key not in default language"),
+            );
+
+            *v = variables::replace_variables(v, &names).unwrap_or_else(|name| {
+                panic!(
+                    "Language '{}' key '{k}' uses the variable '{name}' which is not used by the default language '{default_variant}'.",
+                    variant.name
+                )
+            });
+        }
+    }
+}
+
 /// Parses all property values for templating format arguments.
 fn parse_property_values_for_substitution_format(variants: &mut LinkedHashMap<String, Variant>) {
     for (_, variant) in variants.iter_mut() {
@@ -687,6 +2788,138 @@ fn parse_property_values_for_substitution_format(variants: &mut LinkedHashMap<St
     }
 }
 
+/// Replaces the properties of all pseudo languages with a pseudo localization of the default language.
+/// Only the constant text is pseudo localized, the format arguments are preserved.
+fn generate_pseudo_localizations(
+    default_variant: &str,
+    variants: &mut LinkedHashMap<String, Variant>,
+) {
+    let default_variant_value = variants
+        .get(default_variant)
+        .expect(
+            "unreachable: generate_pseudo_localizations -> variants.get default_variant is none",
+        )
+        .clone();
+
+    for (_, variant) in variants.iter_mut() {
+        let Source::Pseudo(expansion) = variant.source else {
+            continue;
+        };
+
+        for (k, parts) in &default_variant_value.properties_split_by_format_args {
+            let pseudo_parts = pseudo_localize(parts, expansion);
+            let mut value = String::new();
+            for (prefix, index) in &pseudo_parts {
+                value.push_str(prefix);
+                if *index != usize::MAX {
+                    _ = write!(value, "{{{index}}}");
+                }
+            }
+
+            variant.properties.insert(k.clone(), value);
+            variant
+                .properties_split_by_format_args
+                .insert(k.clone(), pseudo_parts);
+        }
+    }
+}
+
+/// Pseudo localizes a value that was already split by format arguments.
+/// Letters are replaced with accented variants, the text is padded by `expansion` percent
+/// and the whole value is enclosed in brackets.
+fn pseudo_localize(parts: &[(String, usize)], expansion: usize) -> Vec<(String, usize)> {
+    let mut result = Vec::with_capacity(parts.len() + 1);
+    let mut text_len = 0usize;
+    for (prefix, index) in parts {
+        let mut pseudo = String::new();
+        if result.is_empty() {
+            pseudo.push('[');
+        }
+
+        for c in prefix.chars() {
+            pseudo.push(pseudo_char(c));
+            text_len += 1;
+        }
+
+        result.push((pseudo, *index));
+    }
+
+    if result.is_empty() {
+        result.push((String::from("["), usize::MAX));
+    } else if result.last().is_none_or(|(_, index)| *index != usize::MAX) {
+        result.push((String::new(), usize::MAX));
+    }
+
+    let (suffix, _) = result
+        .last_mut()
+        .expect("unreachable: pseudo_localize -> result is empty");
+    for _ in 0..(text_len * expansion).div_ceil(100) {
+        suffix.push('~');
+    }
+    suffix.push(']');
+
+    result
+}
+
+/// Maps an ascii letter to an accented variant that looks similar.
+const fn pseudo_char(c: char) -> char {
+    match c {
+        'a' => 'å',
+        'b' => 'ƀ',
+        'c' => 'ç',
+        'd' => 'ð',
+        'e' => 'é',
+        'f' => 'ƒ',
+        'g' => 'ĝ',
+        'h' => 'ĥ',
+        'i' => 'î',
+        'j' => 'ĵ',
+        'k' => 'ķ',
+        'l' => 'ļ',
+        'm' => 'ɱ',
+        'n' => 'ñ',
+        'o' => 'ö',
+        'p' => 'þ',
+        'q' => 'ǫ',
+        'r' => 'ŕ',
+        's' => 'š',
+        't' => 'ţ',
+        'u' => 'û',
+        'v' => 'ṽ',
+        'w' => 'ŵ',
+        'x' => 'ẋ',
+        'y' => 'ý',
+        'z' => 'ž',
+        'A' => 'Å',
+        'B' => 'Ɓ',
+        'C' => 'Ç',
+        'D' => 'Ð',
+        'E' => 'É',
+        'F' => 'Ƒ',
+        'G' => 'Ĝ',
+        'H' => 'Ĥ',
+        'I' => 'Î',
+        'J' => 'Ĵ',
+        'K' => 'Ķ',
+        'L' => 'Ļ',
+        'M' => 'Ṁ',
+        'N' => 'Ñ',
+        'O' => 'Ö',
+        'P' => 'Þ',
+        'Q' => 'Ǫ',
+        'R' => 'Ŕ',
+        'S' => 'Š',
+        'T' => 'Ţ',
+        'U' => 'Û',
+        'V' => 'Ṽ',
+        'W' => 'Ŵ',
+        'X' => 'Ẋ',
+        'Y' => 'Ý',
+        'Z' => 'Ž',
+        other => other,
+    }
+}
+
 /// Gets the maximum format index for every key.
 /// Maximum refers to across all languages.
 fn find_max_format_index_per_key(
@@ -795,6 +3028,72 @@ const fn escape_char_in_variable_name(c: char) -> Option<&'static str> {
     })
 }
 
+/// Is a name the name of the crates `core`, `std` or `alloc`, a primitive type or an item of the prelude?
+/// A module with such a name shadows them, so paths like `core::fmt` or `usize::MAX` no longer compile.
+fn shadows_builtin_name(name: &str) -> bool {
+    matches!(
+        name,
+        "core"
+            | "std"
+            | "alloc"
+            | "bool"
+            | "char"
+            | "str"
+            | "f32"
+            | "f64"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "AsMut"
+            | "AsRef"
+            | "Box"
+            | "Clone"
+            | "Copy"
+            | "Default"
+            | "DoubleEndedIterator"
+            | "Drop"
+            | "Eq"
+            | "Err"
+            | "ExactSizeIterator"
+            | "Extend"
+            | "Fn"
+            | "FnMut"
+            | "FnOnce"
+            | "From"
+            | "FromIterator"
+            | "Into"
+            | "IntoIterator"
+            | "Iterator"
+            | "None"
+            | "Ok"
+            | "Option"
+            | "Ord"
+            | "PartialEq"
+            | "PartialOrd"
+            | "Result"
+            | "Send"
+            | "Sized"
+            | "Some"
+            | "String"
+            | "Sync"
+            | "ToOwned"
+            | "ToString"
+            | "TryFrom"
+            | "TryInto"
+            | "Unpin"
+            | "Vec"
+    )
+}
+
 /// Is a str a rust keyword?
 fn is_keyword(word: &str) -> bool {
     matches!(
@@ -825,7 +3124,7 @@ fn is_keyword(word: &str) -> bool {
             | "mut"
             | "pub"
             | "ref"
-            | "return "
+            | "return"
             | "self"
             | "Self"
             | "static"
@@ -854,100 +3153,314 @@ fn is_keyword(word: &str) -> bool {
     )
 }
 
-/// Escapes variable names to be compatible with characters/names allowed in rust field names.
-/// Provides mapping from prop->var name. The values are guaranteed to be unique.
-fn get_key_to_var_name_mapping(keys: &BTreeSet<String>) -> HashMap<String, String> {
+/// Provides mapping from prop->var name according to the naming strategy. The values are guaranteed to be unique.
+/// Keys in `renames` get the name they are renamed to.
+/// If several other keys result in the same name, then each of them gets a suffix with the hash of the key,
+/// even a key whose name is the key itself. This way, adding a key never renames the statics of keys
+/// that do not collide with it, and a key that collides never silently takes the name of another key.
+fn get_key_to_identifier_mapping(
+    keys: &BTreeSet<String>,
+    renames: &HashMap<String, String>,
+    naming: Naming,
+    collisions: Collisions,
+) -> HashMap<String, String> {
     let mut result = HashMap::new();
-    let mut problem_keys = Vec::new();
-    let mut used_keys = HashSet::new();
-    'next_key: for k in keys {
-        if is_keyword(k) {
-            problem_keys.push(k.clone());
+    let mut renamed_keys_by_name: HashMap<&String, &String> = HashMap::new();
+    let mut keys_by_name: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    for k in keys {
+        if let Some(name) = renames.get(k) {
+            if let Some(other) = renamed_keys_by_name.insert(name, k) {
+                panic!("Key '{other}' and key '{k}' are both renamed to {name}");
+            }
+            result.insert(k.clone(), name.clone());
             continue;
         }
 
-        let Some(first_char) = k.chars().next() else {
-            problem_keys.push(k.clone());
-            continue;
+        let name = match naming {
+            Naming::Keep => None,
+            Naming::ScreamingSnake => convert_key_to_snake_case(k, true),
+            Naming::Snake => convert_key_to_snake_case(k, false),
         };
+        let name = name.unwrap_or_else(|| escape_key(k));
+        keys_by_name.entry(name).or_default().push(k);
+    }
 
-        if first_char.is_ascii_digit() {
-            problem_keys.push(k.clone());
+    let mut names = Vec::new();
+    let mut suffixed = Vec::new();
+    for (name, colliding_keys) in &keys_by_name {
+        if colliding_keys.len() == 1 {
+            names.push((colliding_keys[0], name.clone()));
             continue;
         }
 
-        if first_char != '_' && !first_char.is_xid_start() {
-            problem_keys.push(k.clone());
-            continue;
+        assert!(
+            collisions == Collisions::Suffix,
+            "Keys {} all result in the identifier {name}",
+            colliding_keys
+                .iter()
+                .map(|k| format!("'{k}'"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        // No key keeps the colliding name, so a new key never silently takes the name of another key
+        for k in colliding_keys {
+            let hash = key_hash(k);
+            let suffix = if naming == Naming::Snake {
+                format!("{hash:08x}")
+            } else {
+                format!("{hash:08X}")
+            };
+            suffixed.push((*k, format!("{name}_{suffix}")));
         }
+    }
 
-        for c in k.chars() {
-            if !c.is_xid_continue() {
-                problem_keys.push(k.clone());
-                continue 'next_key;
-            }
+    let mut suffixed_keys_by_name: HashMap<&String, &String> = HashMap::new();
+    for (k, name) in &suffixed {
+        let other = keys_by_name
+            .get(name)
+            .map(|other| other[0])
+            .or_else(|| suffixed_keys_by_name.insert(name, k));
+        if let Some(other) = other {
+            panic!(
+                "Key '{k}' and key '{other}' both result in the identifier {name}, rename one of them"
+            );
+        }
+    }
 
-            if c.is_whitespace() {
-                problem_keys.push(k.clone());
-                continue 'next_key;
-            }
+    for (k, name) in names.into_iter().chain(suffixed) {
+        if let Some(other) = renamed_keys_by_name.get(&name) {
+            panic!("Key '{other}' is renamed to {name}, which is also the name of key '{k}'");
+        }
+        result.insert(k.clone(), name);
+    }
+
+    result
+}
 
-            if escape_char_in_variable_name(c).is_some() {
-                problem_keys.push(k.clone());
-                continue 'next_key;
+/// FNV-1a hash of a key, used to give colliding keys a suffix that does not depend on other keys.
+fn key_hash(key: &str) -> u32 {
+    let mut hash = 0x811c_9dc5_u32;
+    for byte in key.bytes() {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+/// Converts a key to `snake_case` or `SCREAMING_SNAKE_CASE`.
+/// Words are separated by characters that are not allowed in identifiers, by `_` and by case changes
+/// like in `helloWorld` or `HTTPServer`. Names that start with a digit are prefixed with `_`,
+/// keywords are suffixed with `_`. Returns none if the key contains no words.
+fn convert_key_to_snake_case(key: &str, uppercase: bool) -> Option<String> {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    for (idx, c) in chars.iter().copied().enumerate() {
+        if c == '_' || !c.is_xid_continue() {
+            if !word.is_empty() {
+                words.push(mem::take(&mut word));
             }
+            continue;
         }
 
-        result.insert(k.clone(), k.clone());
-        used_keys.insert(k.clone());
+        let previous = idx.checked_sub(1).map(|idx| chars[idx]);
+        let next = chars.get(idx + 1).copied();
+        let starts_word = c.is_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_lowercase()
+                    || previous.is_ascii_digit()
+                    || previous.is_uppercase() && next.is_some_and(char::is_lowercase)
+            });
+
+        if starts_word && !word.is_empty() {
+            words.push(mem::take(&mut word));
+        }
+
+        if uppercase {
+            word.extend(c.to_uppercase());
+        } else {
+            word.extend(c.to_lowercase());
+        }
     }
 
-    'next_key: for k in problem_keys {
-        let mut mapped_key = String::new();
-        mapped_key.push('_');
+    if !word.is_empty() {
+        words.push(word);
+    }
 
-        for c in k.chars() {
-            if let Some(escape) = escape_char_in_variable_name(c) {
-                mapped_key.push_str(escape);
-                continue;
-            }
+    let mut name = words.join("_");
+    if name.is_empty() {
+        return None;
+    }
 
-            if !c.is_xid_continue() {
-                mapped_key.push('_');
-                continue;
-            }
+    if name.starts_with(|c: char| !c.is_xid_start()) {
+        name.insert(0, '_');
+    }
 
-            if c.is_whitespace() {
-                mapped_key.push('_');
-                continue;
-            }
+    if is_keyword(&name) {
+        name.push('_');
+    }
 
-            if c.is_ascii_control() {
-                mapped_key.push('_');
-                continue;
-            }
+    Some(name)
+}
 
-            mapped_key.push(c);
+/// Escapes a key to be compatible with characters/names allowed in rust field names.
+/// Keys that are valid identifiers are not changed, all other keys are prefixed with `_`.
+fn escape_key(k: &str) -> String {
+    if is_valid_variable_name(k) {
+        return k.to_string();
+    }
+
+    let mut mapped_key = String::new();
+    mapped_key.push('_');
+
+    for c in k.chars() {
+        if let Some(escape) = escape_char_in_variable_name(c) {
+            mapped_key.push_str(escape);
+            continue;
         }
 
-        if !used_keys.contains(&mapped_key) {
-            used_keys.insert(mapped_key.clone());
-            result.insert(k, mapped_key);
+        if !c.is_xid_continue() {
+            mapped_key.push('_');
             continue;
         }
 
-        let mut i = 0u128;
-        loop {
-            let fmt = format!("{mapped_key}_{i}");
-            if !used_keys.contains(&fmt) {
-                used_keys.insert(mapped_key.clone());
-                result.insert(k, fmt);
-                continue 'next_key;
-            }
+        if c.is_whitespace() {
+            mapped_key.push('_');
+            continue;
+        }
 
-            i = i.checked_add(1).expect("overflow");
+        if c.is_ascii_control() {
+            mapped_key.push('_');
+            continue;
         }
+
+        mapped_key.push(c);
     }
 
-    result
+    mapped_key
+}
+
+/// Checks if a key can be used as the name of a static without escaping.
+fn is_valid_variable_name(k: &str) -> bool {
+    if is_keyword(k) {
+        return false;
+    }
+
+    let Some(first_char) = k.chars().next() else {
+        return false;
+    };
+
+    if first_char.is_ascii_digit() {
+        return false;
+    }
+
+    if first_char != '_' && !first_char.is_xid_start() {
+        return false;
+    }
+
+    for c in k.chars() {
+        if !c.is_xid_continue() {
+            return false;
+        }
+
+        if c.is_whitespace() {
+            return false;
+        }
+
+        if escape_char_in_variable_name(c).is_some() {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        generate_static_doc, get_key_to_identifier_mapping, Collisions, Naming, Source, Variant,
+        MAX_DOC_TEXT_LENGTH,
+    };
+    use linked_hash_map::LinkedHashMap;
+    use std::collections::{BTreeSet, HashMap};
+
+    /// Creates a language whose only key `key` has the given value.
+    fn variant(name: &str, value: &str) -> Variant {
+        let mut variant = Variant::new(name.to_string(), Source::File(Vec::new()));
+        variant
+            .properties
+            .insert("key".to_string(), value.to_string());
+        variant
+    }
+
+    #[test]
+    fn static_doc() {
+        let mut english = variant("English", "Weld {0} with `{1}`\n");
+        english.comments.insert(
+            "key".to_string(),
+            vec![
+                "Shown above the \"weld\" button.".to_string(),
+                "@rename WELD".to_string(),
+            ],
+        );
+        let german = variant("German", &"a".repeat(MAX_DOC_TEXT_LENGTH + 1));
+
+        let mut variants = LinkedHashMap::new();
+        variants.insert(english.name.clone(), english);
+        variants.insert(german.name.clone(), german);
+
+        let mut output = String::new();
+        generate_static_doc("key", 2, &variants, &mut output);
+        let expected = [
+            r#"#[doc = "Shown above the \"weld\" button."]"#,
+            r#"#[doc = ""]"#,
+            r#"#[doc = "Key: `key`"]"#,
+            r#"#[doc = ""]"#,
+            r#"#[doc = "Format arguments: 2"]"#,
+            r#"#[doc = ""]"#,
+            r#"#[doc = "* English (default): ``Weld {0} with `{1}` ``"]"#,
+            &format!(
+                r#"#[doc = "* German: `{}\u{{2026}}`"]"#,
+                "a".repeat(MAX_DOC_TEXT_LENGTH)
+            ),
+        ];
+        assert_eq!(expected.join("\n") + "\n", output);
+    }
+
+    #[test]
+    fn static_doc_without_comment_and_arguments() {
+        let mut variants = LinkedHashMap::new();
+        variants.insert("English".to_string(), variant("English", "Save"));
+
+        let mut output = String::new();
+        generate_static_doc("key", 0, &variants, &mut output);
+        let expected = [
+            r#"#[doc = "Key: `key`"]"#,
+            r#"#[doc = ""]"#,
+            r#"#[doc = "* English (default): `Save`"]"#,
+        ];
+        assert_eq!(expected.join("\n") + "\n", output);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "both result in the identifier A_B_C_D_E_F_G_H_I_J_K_L_M_N_O_P_0768D733"
+    )]
+    fn colliding_suffixes_are_rejected() {
+        //Both keys have the name A_B_C_D_E_F_G_H_I_J_K_L_M_N_O_P and the hash 0768D733.
+        let keys = [
+            "a_b_c_d:e_f/g/h-i-j:k.l.m_n:o-p",
+            "a-b:c_d.e-f_g_h-i.j:k-l:m:n.o-p",
+        ]
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<BTreeSet<_>>();
+        get_key_to_identifier_mapping(
+            &keys,
+            &HashMap::new(),
+            Naming::ScreamingSnake,
+            Collisions::Suffix,
+        );
+    }
 }
@@ -0,0 +1,102 @@
+//! Conversion of printf style placeholders, like the ones used by Android and Apple, into format arguments.
+
+/// Length modifiers of printf style placeholders, they are ignored.
+const LENGTH_MODIFIERS: &str = "hlqLztj";
+
+/// Conversions of printf style placeholders.
+/// `@` is the object conversion used by Apple.
+const CONVERSIONS: &str = "@sSdDiuUxXoOfFeEgGaAcCbBhHp";
+
+/// Converts all printf style placeholders (`%s`, `%1$s`, `%@`, `%.2f`, ...) of a value into
+/// format arguments (`{0}`, ...). Positional placeholders like `%2$s` use their position,
+/// all other placeholders are numbered in the order of their appearance.
+/// Flags, width and precision are removed, because format arguments only support `Display`.
+/// `%%` is converted to `%`. A `%` that does not start a placeholder is kept.
+pub fn convert(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut next_index = 0usize;
+    let mut rest = value;
+    while let Some(start) = rest.find('%') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(stripped) = rest.strip_prefix("%%") {
+            result.push('%');
+            rest = stripped;
+            continue;
+        }
+
+        let Some((index, length)) = parse_placeholder(rest) else {
+            result.push('%');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let index = index.unwrap_or_else(|| {
+            next_index += 1;
+            next_index - 1
+        });
+
+        result.push('{');
+        result.push_str(&index.to_string());
+        result.push('}');
+        rest = &rest[length..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Parses a placeholder at the start of `value`, which starts with `%`.
+/// Returns the zero based position of a positional placeholder and the length of the placeholder.
+fn parse_placeholder(value: &str) -> Option<(Option<usize>, usize)> {
+    let bytes = value.as_bytes();
+    let mut pos = 1;
+
+    let digits = count(&bytes[pos..], |b| b.is_ascii_digit());
+    let mut index = None;
+    if digits > 0 && bytes.get(pos + digits) == Some(&b'$') {
+        let position = value[pos..pos + digits].parse::<usize>().ok()?;
+        index = Some(position.checked_sub(1)?);
+        pos += digits + 1;
+    }
+
+    // The space flag is not supported, so text like `50% off` is not a placeholder
+    pos += count(&bytes[pos..], |b| b"-+#0',".contains(&b));
+    pos += count(&bytes[pos..], |b| b.is_ascii_digit());
+    if bytes.get(pos) == Some(&b'.') {
+        pos += 1;
+        pos += count(&bytes[pos..], |b| b.is_ascii_digit());
+    }
+
+    pos += count(&bytes[pos..], |b| LENGTH_MODIFIERS.as_bytes().contains(&b));
+    let conversion = *bytes.get(pos)?;
+    if !CONVERSIONS.as_bytes().contains(&conversion) {
+        return None;
+    }
+
+    Some((index, pos + 1))
+}
+
+/// Counts the leading bytes that match a predicate.
+fn count(bytes: &[u8], predicate: impl Fn(u8) -> bool) -> usize {
+    bytes.iter().take_while(|b| predicate(**b)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert;
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!("{0} has {1} files", convert("%s has %d files"));
+        assert_eq!("{1} {0}", convert("%2$s %1$@"));
+        assert_eq!("{0} and {1}", convert("%-5.2f and %#x"));
+        assert_eq!("100%", convert("100%%"));
+    }
+
+    #[test]
+    fn test_text_with_percent_sign() {
+        assert_eq!("50% off", convert("50% off"));
+        assert_eq!("100% sure", convert("100% sure"));
+    }
+}
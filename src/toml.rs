@@ -0,0 +1,51 @@
+//! Reading of TOML resource files.
+use ::toml::value::{Table, Value};
+use std::collections::HashMap;
+
+/// Parses the content of a TOML resource file.
+/// Nested tables are flattened into dotted keys.
+pub fn parse(content: &str) -> Result<HashMap<String, String>, String> {
+    let root: Table = ::toml::from_str(content).map_err(|e| e.to_string())?;
+    let mut result = HashMap::new();
+    flatten("", &root, &mut result)?;
+    Ok(result)
+}
+
+/// Flattens a TOML table into `result`, prefixing all keys with `prefix`.
+fn flatten(
+    prefix: &str,
+    table: &Table,
+    result: &mut HashMap<String, String>,
+) -> Result<(), String> {
+    for (k, v) in table {
+        let key = if prefix.is_empty() {
+            k.clone()
+        } else {
+            format!("{prefix}.{k}")
+        };
+
+        let value = match v {
+            Value::Table(nested) => {
+                flatten(&key, nested, result)?;
+                continue;
+            }
+            Value::String(value) => value.clone(),
+            Value::Integer(value) => value.to_string(),
+            Value::Float(value) => value.to_string(),
+            Value::Boolean(value) => value.to_string(),
+            Value::Datetime(value) => value.to_string(),
+            Value::Array(_) => {
+                return Err(format!(
+                    "key '{key}' has an array value which is not supported"
+                ))
+            }
+        };
+
+        //A dotted key like "a.b" and a table [a] with the key b result in the same key.
+        if result.insert(key.clone(), value).is_some() {
+            return Err(format!("key '{key}' exists more than once"));
+        }
+    }
+
+    Ok(())
+}
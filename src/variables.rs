@@ -0,0 +1,52 @@
+//! Named variables in values, like `{ $name }` in fluent messages or placeholder elements in XLIFF files.
+//! Readers mark variables in values with private use characters.
+//! Once all languages have been read, variables are replaced with format arguments.
+
+/// Marks the start of a named variable in a value.
+const VARIABLE_START: char = '\u{F0000}';
+
+/// Marks the end of a named variable in a value.
+const VARIABLE_END: char = '\u{F0001}';
+
+/// Appends a named variable to a value.
+#[cfg(any(feature = "fluent", feature = "xml"))]
+pub fn push(name: &str, result: &mut String) {
+    result.push(VARIABLE_START);
+    result.push_str(name);
+    result.push(VARIABLE_END);
+}
+
+/// Gets the names of all variables in a value in the order of their first appearance.
+pub fn variable_names(value: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    for part in value.split(VARIABLE_START).skip(1) {
+        let name = part.split(VARIABLE_END).next().unwrap_or_default();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+/// Replaces all variables in a value with format arguments (`{0}`).
+/// The index of a variable is its position in `names`.
+/// returns the name of the first variable that is not in `names` as error.
+pub fn replace_variables(value: &str, names: &[&str]) -> Result<String, String> {
+    let mut parts = value.split(VARIABLE_START);
+    let mut result = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let (name, rest) = part.split_once(VARIABLE_END).unwrap_or((part, ""));
+        let index = names
+            .iter()
+            .position(|candidate| *candidate == name)
+            .ok_or_else(|| name.to_string())?;
+
+        result.push('{');
+        result.push_str(index.to_string().as_str());
+        result.push('}');
+        result.push_str(rest);
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,152 @@
+//! Reading of XLIFF 1.2 and 2.0 files.
+use crate::variables;
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+
+/// Parses the content of a XLIFF 1.2 or 2.0 file.
+/// The id of a `trans-unit` (1.2) or `unit` (2.0) is the key, the text of its target is the value.
+/// If the target is missing or not translated yet, then the source is used if `untranslated_as_source` is set.
+/// Otherwise, the unit is treated as not translated.
+/// The placeholder elements `x` and `ph` become variables named by their id.
+pub fn parse(
+    content: &str,
+    untranslated_as_source: bool,
+) -> Result<HashMap<String, String>, String> {
+    let document = Document::parse(content).map_err(|e| e.to_string())?;
+    let root = document.root_element();
+    if root.tag_name().name() != "xliff" {
+        return Err(format!(
+            "root element must be xliff, but is {}",
+            root.tag_name().name()
+        ));
+    }
+
+    let mut result = HashMap::new();
+    for unit in root.descendants() {
+        let translated = match unit.tag_name().name() {
+            "trans-unit" => translate_unit_1_2(unit)?,
+            "unit" => translate_unit_2_0(unit)?,
+            _ => continue,
+        };
+
+        let id = unit
+            .attribute("id")
+            .ok_or_else(|| format!("{} without id", unit.tag_name().name()))?;
+
+        let value = match translated {
+            Unit::Translated(value) => value,
+            Unit::Untranslated(source) if untranslated_as_source => source,
+            Unit::Untranslated(_) => continue,
+        };
+
+        if result.insert(id.to_string(), value).is_some() {
+            return Err(format!("unit '{id}' exists more than once"));
+        }
+    }
+
+    Ok(result)
+}
+
+/// Text of a unit.
+enum Unit {
+    /// The text of the target.
+    Translated(String),
+    /// The unit is not translated yet, contains the text of the source.
+    Untranslated(String),
+}
+
+/// Gets the text of a XLIFF 1.2 `trans-unit`.
+fn translate_unit_1_2(unit: Node) -> Result<Unit, String> {
+    let source = child_text(unit, "source")?.unwrap_or_default();
+    let Some(target_node) = child(unit, "target") else {
+        return Ok(Unit::Untranslated(source));
+    };
+
+    let untranslated = matches!(
+        target_node.attribute("state"),
+        Some("new" | "needs-translation" | "needs-adaptation" | "needs-l10n")
+    );
+
+    let target = element_text(target_node)?;
+    if untranslated || target.is_empty() {
+        return Ok(Unit::Untranslated(source));
+    }
+
+    Ok(Unit::Translated(target))
+}
+
+/// Gets the text of a XLIFF 2.0 `unit` by joining the text of all its segments.
+fn translate_unit_2_0(unit: Node) -> Result<Unit, String> {
+    let mut source = String::new();
+    let mut target = String::new();
+    let mut untranslated = false;
+    for segment in unit.children().filter(Node::is_element) {
+        let ignorable = match segment.tag_name().name() {
+            "segment" => false,
+            "ignorable" => true,
+            _ => continue,
+        };
+
+        let segment_source = child_text(segment, "source")?.unwrap_or_default();
+        let segment_target = child_text(segment, "target")?;
+        //Ignorable whitespace between segments usually has no target.
+        untranslated |= !ignorable
+            && (segment.attribute("state") == Some("initial")
+                || segment_target.is_none() && !segment_source.is_empty());
+
+        source.push_str(&segment_source);
+        target.push_str(&segment_target.unwrap_or(segment_source));
+    }
+
+    if untranslated || target.is_empty() {
+        return Ok(Unit::Untranslated(source));
+    }
+
+    Ok(Unit::Translated(target))
+}
+
+/// Finds the first child element with the given name.
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+/// Gets the text of the first child element with the given name.
+fn child_text(node: Node, name: &str) -> Result<Option<String>, String> {
+    child(node, name).map(element_text).transpose()
+}
+
+/// Gets the text of an element, converting placeholder elements into variables.
+fn element_text(element: Node) -> Result<String, String> {
+    let mut result = String::new();
+    append_element_text(element, &mut result)?;
+    Ok(result)
+}
+
+/// Appends the text of all children of an element to `result`.
+fn append_element_text(element: Node, result: &mut String) -> Result<(), String> {
+    for node in element.children() {
+        if node.is_text() {
+            result.push_str(node.text().unwrap_or_default());
+            continue;
+        }
+
+        if !node.is_element() {
+            continue;
+        }
+
+        match node.tag_name().name() {
+            "x" | "ph" => {
+                let id = node
+                    .attribute("id")
+                    .ok_or_else(|| format!("{} without id", node.tag_name().name()))?;
+                variables::push(id, result);
+            }
+            //Codes of the original format (like <b>) that cannot be represented in a value.
+            "bx" | "ex" | "bpt" | "ept" | "it" | "sc" | "ec" => {}
+            _ => append_element_text(node, result)?,
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Reading of YAML resource files.
+use std::collections::HashMap;
+use yaml_rust2::{yaml::Hash, Yaml, YamlLoader};
+
+/// Parses the content of a YAML resource file.
+/// The root must be a mapping, nested mappings are flattened into dotted keys.
+/// Keys with a null value are treated as not translated.
+pub fn parse(content: &str) -> Result<HashMap<String, String>, String> {
+    let mut documents = YamlLoader::load_from_str(content).map_err(|e| e.to_string())?;
+    if documents.len() > 1 {
+        return Err("the file must not contain more than one document".to_string());
+    }
+
+    let mut result = HashMap::new();
+    match documents.pop() {
+        None | Some(Yaml::Null) => {}
+        Some(Yaml::Hash(root)) => flatten("", &root, &mut result)?,
+        Some(_) => return Err("the root of the file must be a mapping".to_string()),
+    }
+
+    Ok(result)
+}
+
+/// Converts a scalar to its string representation.
+fn scalar_to_string(yaml: &Yaml) -> Option<String> {
+    match yaml {
+        Yaml::String(value) | Yaml::Real(value) => Some(value.clone()),
+        Yaml::Integer(value) => Some(value.to_string()),
+        Yaml::Boolean(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Flattens a YAML mapping into `result`, prefixing all keys with `prefix`.
+fn flatten(prefix: &str, hash: &Hash, result: &mut HashMap<String, String>) -> Result<(), String> {
+    for (k, v) in hash {
+        let Some(k) = scalar_to_string(k) else {
+            return Err(format!(
+                "keys must be scalars, but a key of '{prefix}' is {k:?}"
+            ));
+        };
+
+        let key = if prefix.is_empty() {
+            k
+        } else {
+            format!("{prefix}.{k}")
+        };
+
+        let value = match v {
+            Yaml::Null => continue,
+            Yaml::Hash(nested) => {
+                flatten(&key, nested, result)?;
+                continue;
+            }
+            Yaml::Array(_) => {
+                return Err(format!(
+                    "key '{key}' has a sequence value which is not supported"
+                ))
+            }
+            other => scalar_to_string(other)
+                .ok_or_else(|| format!("key '{key}' has an unsupported value {other:?}"))?,
+        };
+
+        if result.insert(key.clone(), value).is_some() {
+            return Err(format!("key '{key}' exists more than once"));
+        }
+    }
+
+    Ok(())
+}
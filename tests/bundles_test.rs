@@ -0,0 +1,102 @@
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/english.properties";
+        German="tests/german.properties";
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[prefix = menu]
+        #[selection = get_i18n_language]
+        German="tests/bundles_german.properties";
+        English="tests/bundles_english.properties";
+    }
+}
+
+pub mod prefixes {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[prefix = first]
+        #[dotted_keys = modules]
+        English="tests/modules_english.json";
+        German="tests/modules_german.properties";
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[prefix = second]
+        #[dotted_keys = modules]
+        English="tests/modules_english.json";
+        German="tests/modules_german.properties";
+    }
+}
+
+pub mod other {
+    pub enum Region {
+        Europe,
+    }
+
+    trivial_i18n::i18n! {
+        Region;
+        #[prefix = region]
+        Europe="tests/english.properties";
+    }
+
+    trivial_i18n::i18n! {
+        super::i18n::Language;
+        #[selection = super::i18n::get_i18n_language]
+        English="tests/bundles_english.properties";
+        German="tests/bundles_german.properties";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(i18n::Language::German);
+    assert!(matches!(i18n::get_i18n_language(), i18n::Language::German));
+    assert_eq!("Farbe", i18n::COLOR.as_str());
+    assert_eq!("Öffnen", i18n::MENU_OPEN.as_str());
+    assert_eq!("Öffnen", other::OPEN.as_str());
+
+    i18n::set_i18n_language(i18n::Language::English);
+    assert!(matches!(i18n::get_i18n_language(), i18n::Language::English));
+    assert_eq!("Colour", i18n::COLOR.as_str());
+    assert_eq!("Open", i18n::MENU_OPEN.as_str());
+    assert_eq!("Open", other::OPEN.as_str());
+
+    other::region_set_i18n_language(other::Region::Europe);
+    assert!(matches!(
+        other::region_get_i18n_language(),
+        other::Region::Europe
+    ));
+    assert_eq!("Colour", other::REGION_COLOR.as_str());
+
+    let _: i18n::MenuI18NValue<0> = i18n::MENU_OPEN;
+    assert!(!i18n::menu_is_i18n_language_rtl());
+}
+
+#[test]
+pub fn test_same_enum_different_prefixes() {
+    //Every invocation has its own selected language, statics and modules.
+    prefixes::first_set_i18n_language(prefixes::Language::German);
+    prefixes::second_set_i18n_language(prefixes::Language::English);
+    assert_eq!("Willkommen", prefixes::first_title.as_str());
+    assert_eq!("Nicht gefunden", prefixes::first_error::_404.as_str());
+    assert_eq!("Welcome", prefixes::second_title.as_str());
+    assert_eq!("Not found", prefixes::second_error::_404.as_str());
+    assert!(matches!(
+        prefixes::second_get_i18n_language(),
+        prefixes::Language::English
+    ));
+}
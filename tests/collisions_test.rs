@@ -0,0 +1,32 @@
+pub mod suffix {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/collisions_english.properties";
+    }
+}
+
+pub mod strict {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[collisions = error]
+        English="tests/collisions_strict.properties";
+    }
+}
+
+#[test]
+pub fn test() {
+    //All colliding keys get a suffix with their hash, even the key that is a valid name.
+    assert_eq!("Escaped", suffix::_a_MINUS_b_8549C47F.as_str());
+    assert_eq!("Dash", suffix::_a_MINUS_b_2A89DF63.as_str());
+    assert_eq!("Plain", suffix::plain.as_str());
+
+    assert_eq!("Other", strict::_other_MINUS_key.as_str());
+}
@@ -0,0 +1,18 @@
+pub mod i18n {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/deprecation_english.properties";
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+pub fn test() {
+    assert_eq!("Welcome", i18n::NEW_TITLE.as_str());
+    assert_eq!("Welcome!", i18n::OLD_TITLE.as_str());
+    assert_eq!("Retired", i18n::RETIRED.as_str());
+}
@@ -0,0 +1,44 @@
+use crate::i18n::{I18NLanguage, Language};
+
+pub mod i18n {
+    #[derive(trivial_i18n::I18n)]
+    #[i18n(dir = "tests", naming = screaming_snake)]
+    pub enum Language {
+        #[i18n(file = "german.properties", name = "Deutsch", tag = "de")]
+        German,
+        /// The default language.
+        #[i18n(file = "english.properties", default)]
+        English,
+        #[i18n(file = "swiss_german.properties", fallback = German, tag = "de-CH")]
+        SwissGerman,
+        #[i18n(pseudo = 100)]
+        Pseudo,
+        Other,
+    }
+}
+
+#[test]
+pub fn test() {
+    assert_eq!("Colour", i18n::COLOR.as_str());
+    assert_eq!("Colour", i18n::COLOR.default_str());
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Farbe", i18n::COLOR.as_str());
+    assert_eq!("Deutsch", Language::German.display_name());
+
+    i18n::set_i18n_language(Language::SwissGerman);
+    assert_eq!("Strasse", i18n::STREET.as_str());
+    assert_eq!("Farbe", i18n::COLOR.as_str());
+    assert!(matches!(
+        Language::negotiate("de-CH"),
+        Some(Language::SwissGerman)
+    ));
+
+    i18n::set_i18n_language(Language::Other);
+    assert_eq!("Colour", i18n::COLOR.as_str());
+
+    i18n::set_i18n_language(Language::Pseudo);
+    let pseudo = i18n::COLOR.as_str();
+    assert!(pseudo.starts_with('[') && pseudo.ends_with(']'));
+    assert_ne!("[Colour]", pseudo);
+}
@@ -0,0 +1,40 @@
+use crate::i18n::{I18NLanguage, Language};
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+        SwissGerman,
+        EnGb,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[dir = "tests/discovery"]
+        #[pattern = "messages_{Lang}.properties"]
+        English;
+        SwissGerman, German;
+        EnGb, English [tag = "en-GB"];
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Hello John!", i18n::GREETING.format(("John",)));
+    assert_eq!("Color", i18n::COLOR.as_str());
+
+    //German is not declared, it is discovered without fallbacks.
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Hallo John!", i18n::GREETING.format(("John",)));
+    assert_eq!("Speichern", i18n::SAVE.as_str());
+
+    i18n::set_i18n_language(Language::SwissGerman);
+    assert_eq!("Grüezi John!", i18n::GREETING.format(("John",)));
+    assert_eq!("Farbe", i18n::COLOR.as_str());
+
+    i18n::set_i18n_language(Language::EnGb);
+    assert_eq!("Colour", i18n::COLOR.as_str());
+    assert_eq!("Save", i18n::SAVE.as_str());
+    assert_eq!(Some("en-GB"), Language::EnGb.language_tag());
+}
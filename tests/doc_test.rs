@@ -0,0 +1,32 @@
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/doc_english.properties";
+        German="tests/doc_german.properties", English;
+    }
+}
+
+#[test]
+pub fn test() {
+    assert_eq!(
+        "Weld the seam of A with B",
+        i18n::WELD_SEAM.format(("A", "B"))
+    );
+    assert_eq!(
+        "This text is much longer than what fits into the documentation of a static, so it gets truncated.",
+        i18n::LONG_TEXT.as_str()
+    );
+
+    i18n::set_i18n_language(i18n::Language::German);
+    assert_eq!(
+        "Schweißnaht von A mit B schweißen",
+        i18n::WELD_SEAM.format(("A", "B"))
+    );
+    assert_eq!("Kurz", i18n::LONG_TEXT.as_str());
+    assert_eq!("Ein \"zitierter\" Text", i18n::quoted.as_str());
+}
@@ -0,0 +1,96 @@
+use crate::i18n::{I18NLanguage, Language};
+
+pub mod i18n {
+    #[derive(Debug, Eq, PartialEq)]
+    pub enum Language {
+        English,
+        German,
+        SwissGerman,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[env_init = true]
+        English="tests/english.properties" [tag = "en"];
+        German="tests/german.properties" [tag = "de"];
+        SwissGerman="tests/swiss_german.properties", German [tag = "de-CH"];
+    }
+}
+
+pub mod prelude {
+    #[derive(Debug, Eq, PartialEq)]
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[env_init = true]
+        English="tests/prelude_english.properties" [tag = "en"];
+    }
+}
+
+#[test]
+pub fn test() {
+    assert_eq!(
+        Some(Language::SwissGerman),
+        Language::from_env_values(None, None, Some("de_CH.UTF-8"), None)
+    );
+    assert_eq!(
+        Some(Language::German),
+        Language::from_env_values(
+            Some("de_DE.UTF-8"),
+            Some("en_US.UTF-8"),
+            Some("fr_FR.UTF-8"),
+            None
+        )
+    );
+    assert_eq!(
+        Some(Language::English),
+        Language::from_env_values(Some(""), Some("en_US.UTF-8"), Some("de_DE.UTF-8"), None)
+    );
+    assert_eq!(
+        Some(Language::German),
+        Language::from_env_values(None, None, Some("en_US.UTF-8"), Some("fr:de:en"))
+    );
+    //LANGUAGE is ignored for the C locale.
+    assert_eq!(
+        None,
+        Language::from_env_values(None, None, Some("C.UTF-8"), Some("de"))
+    );
+    assert_eq!(
+        None,
+        Language::from_env_values(None, None, None, Some("de"))
+    );
+    assert_eq!(
+        None,
+        Language::from_env_values(None, None, Some("fr_FR.UTF-8"), None)
+    );
+}
+
+#[test]
+pub fn test_init_from_env() {
+    std::env::set_var("LC_ALL", "de_CH.UTF-8");
+    assert!(i18n::init_i18n_language_from_env());
+    assert_eq!("Strasse", i18n::STREET.as_str());
+
+    std::env::set_var("LC_ALL", "fr_FR.UTF-8");
+    assert!(!i18n::init_i18n_language_from_env());
+    assert_eq!("Strasse", i18n::STREET.as_str());
+}
+
+#[test]
+pub fn test_keys_named_like_the_prelude() {
+    //The statics None and Some do not shadow the variants of Option in the generated code.
+    use crate::prelude::I18NLanguage as _;
+    assert_eq!(Some("en"), prelude::Language::English.language_tag());
+    assert_eq!(
+        Some(prelude::Language::English),
+        prelude::Language::negotiate("en-US")
+    );
+    assert_eq!(
+        None,
+        prelude::Language::from_env_values(None, None, Some("de_DE.UTF-8"), None)
+    );
+    assert_eq!("None", prelude::None.as_str());
+}
@@ -0,0 +1,13 @@
+# Simple things are simple.
+-brand-name = Weldmaster
+
+hello-user = Hello, { $userName }!
+welcome = Welcome to { -brand-name }, { $userName }.
+shared-photos = { $userName } added { $photoCount } new photos.
+about = About { hello-user }
+login-input = Predefined value
+    .placeholder = email@example.com
+literal = { "{" }braces{ "}" } and { 42 }
+multiline =
+    First line
+    second line
@@ -0,0 +1,6 @@
+-brand-name = Schweissmeister
+
+hello-user = Hallo, { $userName }!
+shared-photos = { $photoCount } neue Fotos von { $userName }.
+login-input = Vordefinierter Wert
+    .placeholder = email@beispiel.ch
@@ -0,0 +1,53 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/fluent_english.ftl";
+        German="tests/fluent_german.ftl";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Hello, John!", i18n::_hello_MINUS_user.format(("John",)));
+    assert_eq!(
+        "Welcome to Weldmaster, John.",
+        i18n::welcome.format(("John",))
+    );
+    assert_eq!(
+        "John added 3 new photos.",
+        i18n::_shared_MINUS_photos.format(("John", 3))
+    );
+    assert_eq!("About Hello, John!", i18n::about.format(("John",)));
+    assert_eq!("Predefined value", i18n::_login_MINUS_input.as_str());
+    assert_eq!(
+        "email@example.com",
+        i18n::_login_MINUS_input_DOT_placeholder.as_str()
+    );
+    assert_eq!("{braces} and 42", i18n::literal.as_str());
+    assert_eq!("First line\nsecond line", i18n::multiline.as_str());
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Hallo, John!", i18n::_hello_MINUS_user.format(("John",)));
+    //The order of the arguments is defined by the default language.
+    assert_eq!(
+        "3 neue Fotos von John.",
+        i18n::_shared_MINUS_photos.format(("John", 3))
+    );
+    assert_eq!("About Hallo, John!", i18n::about.format(("John",)));
+    assert_eq!(
+        "email@beispiel.ch",
+        i18n::_login_MINUS_input_DOT_placeholder.as_str()
+    );
+    assert_eq!(
+        "Welcome to Weldmaster, John.",
+        i18n::welcome.format(("John",))
+    );
+}
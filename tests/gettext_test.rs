@@ -0,0 +1,63 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+        French,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/gettext_english.pot";
+        German="tests/gettext_german.po";
+        French="tests/gettext_french.mo";
+    }
+}
+
+pub mod fuzzy {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[fuzzy = include]
+        English="tests/gettext_english.pot";
+        German="tests/gettext_german.po";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Open", i18n::Open.as_str());
+    assert_eq!(
+        "Save a.txt",
+        i18n::_Save_SPACE__CURLY_BRACKET_OPEN_0_CURLY_BRACKET_CLOSE_.format(("a.txt",))
+    );
+    assert_eq!("File", i18n::_menu_DOT_File.as_str());
+    assert_eq!("Hello,\nnice to see you!", i18n::GREETING.as_str());
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Öffnen", i18n::Open.as_str());
+    //Fuzzy entries are skipped by default.
+    assert_eq!(
+        "Save a.txt",
+        i18n::_Save_SPACE__CURLY_BRACKET_OPEN_0_CURLY_BRACKET_CLOSE_.format(("a.txt",))
+    );
+    assert_eq!("Datei", i18n::_menu_DOT_File.as_str());
+    //Untranslated entries use the fallback.
+    assert_eq!("Hello,\nnice to see you!", i18n::GREETING.as_str());
+
+    i18n::set_i18n_language(Language::French);
+    assert_eq!("Ouvrir", i18n::Open.as_str());
+    assert_eq!("Fichier", i18n::_menu_DOT_File.as_str());
+
+    fuzzy::set_i18n_language(fuzzy::Language::German);
+    assert_eq!(
+        "a.txt speichern?",
+        fuzzy::_Save_SPACE__CURLY_BRACKET_OPEN_0_CURLY_BRACKET_CLOSE_.format(("a.txt",))
+    );
+}
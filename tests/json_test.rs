@@ -0,0 +1,39 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+        French,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/json_english.json";
+        German="tests/json_german.json";
+        French="tests/json_french.txt" [format = "json"];
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Welcome", i18n::title.as_str());
+    assert_eq!("Open", i18n::_menu_DOT_file_DOT_open.as_str());
+    assert_eq!(
+        "Save a.txt",
+        i18n::_menu_DOT_file_DOT_save.format(("a.txt",))
+    );
+    assert_eq!("3", i18n::count.as_str());
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Willkommen", i18n::title.as_str());
+    assert_eq!("Open", i18n::_menu_DOT_file_DOT_open.as_str());
+    assert_eq!(
+        "a.txt speichern",
+        i18n::_menu_DOT_file_DOT_save.format(("a.txt",))
+    );
+
+    i18n::set_i18n_language(Language::French);
+    assert_eq!("Ouvrir", i18n::_menu_DOT_file_DOT_open.as_str());
+}
@@ -0,0 +1,68 @@
+use crate::i18n::{I18NLanguage, Language};
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+        SwissGerman,
+        Arabic,
+        Other,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/english.properties" [name = "English", tag = "en"];
+        German="tests/german.properties" [name = "Deutsch", tag = "de"];
+        SwissGerman="tests/swiss_german.properties", German [name = "Schwiizerdütsch", tag = "de-CH"];
+        Arabic="tests/us_english.properties" [name = "\u{627}\u{644}\u{639}\u{631}\u{628}\u{64A}\u{629}", rtl];
+    }
+}
+
+pub mod own_methods {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    impl Language {
+        pub const fn display_name(&self) -> &'static str {
+            "own"
+        }
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/english.properties";
+        German="tests/german.properties" [name = "Deutsch"];
+    }
+}
+
+#[test]
+pub fn test() {
+    assert_eq!("English", Language::English.display_name());
+    assert_eq!(Some("en"), Language::English.language_tag());
+    assert!(!Language::English.is_rtl());
+
+    assert_eq!("Deutsch", Language::German.display_name());
+    assert_eq!(Some("de"), Language::German.language_tag());
+
+    assert_eq!("Schwiizerdütsch", Language::SwissGerman.display_name());
+    assert_eq!(Some("de-CH"), Language::SwissGerman.language_tag());
+    assert!(!Language::SwissGerman.is_rtl());
+
+    assert_eq!("العربية", Language::Arabic.display_name());
+    assert_eq!(None, Language::Arabic.language_tag());
+    assert!(Language::Arabic.is_rtl());
+
+    //Languages that are not part of the macro invocation behave like the default language.
+    assert_eq!("English", Language::Other.display_name());
+    assert_eq!(Some("en"), Language::Other.language_tag());
+    assert!(!Language::Other.is_rtl());
+
+    //The methods of the enum itself are not affected.
+    assert_eq!("own", own_methods::Language::German.display_name());
+    assert_eq!(
+        "Deutsch",
+        own_methods::I18NLanguage::display_name(&own_methods::Language::German)
+    );
+}
@@ -0,0 +1,61 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+        French,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/mobile_english.xml";
+        German="tests/mobile_german.strings";
+        French="tests/mobile_french.strings";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Weldmaster", i18n::app_name.as_str());
+    assert_eq!(
+        "Welcome, John! You have 3 new messages.",
+        i18n::welcome.format(("John", 3))
+    );
+    assert_eq!(
+        "Copied a.txt to /tmp.",
+        i18n::copied.format(("a.txt", "/tmp"))
+    );
+    assert_eq!("Don't panic, it's \"fine\"", i18n::apostrophe.as_str());
+    assert_eq!("  spaces  kept  ", i18n::quoted.as_str());
+    assert_eq!("Tap Save to continue at 50%", i18n::styled.format((50,)));
+    assert_eq!("100%s", i18n::not_formatted.as_str());
+    assert_eq!("2 photos", i18n::photos.format((2,)));
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!(
+        "Willkommen, John! Sie haben 3 neue Nachrichten.",
+        i18n::welcome.format(("John", 3))
+    );
+    assert_eq!(
+        "Tippen Sie auf Speichern, um bei 50% fortzufahren",
+        i18n::styled.format((50,))
+    );
+    assert_eq!("2 Fotos", i18n::photos.format((2,)));
+    assert_eq!(
+        "Copied a.txt to /tmp.",
+        i18n::copied.format(("a.txt", "/tmp"))
+    );
+
+    i18n::set_i18n_language(Language::French);
+    assert_eq!(
+        "Bienvenue, John ! Vous avez 3 nouveaux messages.",
+        i18n::welcome.format(("John", 3))
+    );
+    assert_eq!(
+        "/tmp : a.txt copié.",
+        i18n::copied.format(("a.txt", "/tmp"))
+    );
+    assert_eq!("Pas de panique,\nc'est \"bien\"", i18n::apostrophe.as_str());
+}
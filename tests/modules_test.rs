@@ -0,0 +1,54 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[dotted_keys = modules]
+        English="tests/modules_english.json";
+        German="tests/modules_german.properties";
+    }
+}
+
+pub mod builtin {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[dotted_keys = modules]
+        #[naming = keep]
+        English="tests/modules_builtin.properties";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Welcome", i18n::title.as_str());
+    assert_eq!("Open", i18n::menu::file::open.as_str());
+    assert_eq!("Save a.txt", i18n::menu::file::save.format(("a.txt",)));
+    assert_eq!("Menu type", i18n::menu::_type.as_str());
+    assert_eq!("Not found", i18n::error::_404.as_str());
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Willkommen", i18n::title.as_str());
+    assert_eq!("Open", i18n::menu::file::open.as_str());
+    assert_eq!("a.txt speichern", i18n::menu::file::save.format(("a.txt",)));
+    assert_eq!("Nicht gefunden", i18n::error::_404.as_str());
+}
+
+#[test]
+pub fn test_builtin_names() {
+    //Modules that would shadow core, std, primitive types or the prelude are escaped.
+    assert_eq!("Core", builtin::core_::title.as_str());
+    assert_eq!("Maximum", builtin::usize_::max.as_str());
+    assert_eq!("Empty", builtin::str_::empty.as_str());
+    assert_eq!("Input", builtin::std_::io.as_str());
+    assert_eq!("Nothing", builtin::Option_::none.as_str());
+}
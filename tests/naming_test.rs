@@ -0,0 +1,68 @@
+pub mod screaming {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[naming = screaming_snake]
+        English="tests/naming_english.properties";
+    }
+}
+
+pub mod snake {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[naming = snake]
+        English="tests/naming_english.properties";
+    }
+}
+
+pub mod modules {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[naming = screaming_snake]
+        #[dotted_keys = modules]
+        English="tests/naming_english.properties";
+    }
+}
+
+#[test]
+pub fn test_screaming_snake() {
+    //Colliding keys get a suffix with the hash of the key.
+    assert_eq!("Hello World", screaming::HELLO_WORLD_95E3EAC1.as_str());
+    assert_eq!("hello world", screaming::HELLO_WORLD_E6C136A8.as_str());
+    assert_eq!("Server URL", screaming::HTTP_SERVER_URL.as_str());
+    assert_eq!("Open", screaming::MENU_FILE_OPEN.as_str());
+    assert_eq!("First place", screaming::_1ST_PLACE.as_str());
+    assert_eq!("Type", screaming::TYPE.as_str());
+    assert_eq!("Error 404", screaming::ERROR_CODE404.as_str());
+    assert_eq!("Return", screaming::RETURN.as_str());
+}
+
+#[test]
+pub fn test_snake() {
+    //Colliding keys get a suffix with their hash, even the key that is already in snake_case.
+    assert_eq!("hello world", snake::hello_world_e6c136a8.as_str());
+    assert_eq!("Hello World", snake::hello_world_95e3eac1.as_str());
+    assert_eq!("Server URL", snake::http_server_url.as_str());
+    assert_eq!("Type", snake::type_.as_str());
+    assert_eq!("Return", snake::return_.as_str());
+    assert_eq!("Back", snake::menu_return_label.as_str());
+}
+
+#[test]
+pub fn test_modules() {
+    assert_eq!("Open", modules::menu::file::OPEN.as_str());
+    assert_eq!("Server URL", modules::http_server::URL.as_str());
+    assert_eq!("First place", modules::_1st::PLACE.as_str());
+    assert_eq!("Back", modules::menu::return_::LABEL.as_str());
+}
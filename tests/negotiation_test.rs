@@ -0,0 +1,79 @@
+use crate::i18n::{I18NLanguage, Language};
+use crate::untagged::I18NLanguage as _;
+
+pub mod i18n {
+    #[derive(Debug, Eq, PartialEq)]
+    pub enum Language {
+        English,
+        UsaEnglish,
+        German,
+        SwissGerman,
+        Other,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/english.properties" [tag = "en", tag = "en-GB"];
+        UsaEnglish="tests/us_english.properties" [tag = "en-US"];
+        German="tests/german.properties" [tag = "de-DE"];
+        SwissGerman="tests/swiss_german.properties", German [tag = "de-CH"];
+    }
+}
+
+pub mod untagged {
+    #[derive(Debug, Eq, PartialEq)]
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/english.properties";
+    }
+}
+
+#[test]
+pub fn test() {
+    assert_eq!(Some("en"), Language::English.language_tag());
+
+    assert_eq!(Some(Language::English), Language::from_locale_tag("en"));
+    assert_eq!(Some(Language::English), Language::from_locale_tag("EN-gb"));
+    assert_eq!(Some(Language::English), Language::from_locale_tag("en-AU"));
+    assert_eq!(
+        Some(Language::UsaEnglish),
+        Language::from_locale_tag("en_US.UTF-8")
+    );
+    assert_eq!(
+        Some(Language::SwissGerman),
+        Language::from_locale_tag("de_CH.UTF-8@euro")
+    );
+    assert_eq!(
+        Some(Language::SwissGerman),
+        Language::from_locale_tag("de-CH-x-zurich")
+    );
+    //Only the primary subtag matches, German is preferred because Swiss German falls back to it.
+    assert_eq!(Some(Language::German), Language::from_locale_tag("de"));
+    assert_eq!(Some(Language::German), Language::from_locale_tag("de-AT"));
+    assert_eq!(None, Language::from_locale_tag("fr-FR"));
+    assert_eq!(None, Language::from_locale_tag("*"));
+    assert_eq!(None, Language::from_locale_tag(""));
+
+    assert_eq!(
+        Some(Language::SwissGerman),
+        Language::negotiate("de-CH, de;q=0.9, en;q=0.8")
+    );
+    assert_eq!(
+        Some(Language::UsaEnglish),
+        Language::negotiate("fr-CH, fr;q=0.9, en-US;q=0.8, de;q=0.7, *;q=0.5")
+    );
+    assert_eq!(
+        Some(Language::German),
+        Language::negotiate("en;q=0.5, de-AT;q=0.8")
+    );
+    assert_eq!(Some(Language::English), Language::negotiate("en, de"));
+    assert_eq!(None, Language::negotiate("de;q=0, fr"));
+    assert_eq!(None, Language::negotiate(""));
+
+    assert_eq!(None, untagged::Language::from_locale_tag("en"));
+    assert_eq!(None, untagged::Language::negotiate("en"));
+}
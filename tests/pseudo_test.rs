@@ -0,0 +1,32 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        Pseudo,
+        LongPseudo,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/pseudo_english.properties";
+        Pseudo=pseudo;
+        LongPseudo=pseudo(100);
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Hello John!", i18n::HELLO.format(("John",)));
+
+    i18n::set_i18n_language(Language::Pseudo);
+    assert_eq!("[Ĥéļļö {0}!~~~]", i18n::HELLO.as_str());
+    assert_eq!("[Ĥéļļö John!~~~]", i18n::HELLO.format(("John",)));
+    assert_eq!("[{0}]", i18n::NAME.as_str());
+    assert_eq!("[John]", i18n::NAME.format(("John",)));
+    assert_eq!("[]", i18n::EMPTY.as_str());
+
+    i18n::set_i18n_language(Language::LongPseudo);
+    assert_eq!("[Ĥéļļö John!~~~~~~~]", i18n::HELLO.format(("John",)));
+}
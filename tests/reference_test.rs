@@ -0,0 +1,35 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/reference_english.properties";
+        German="tests/reference_german.properties";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Weldmaster 2000", i18n::PRODUCT_VERSION.as_str());
+    assert_eq!(
+        "Welcome to Weldmaster 2000, John!",
+        i18n::WELCOME.format(("John",))
+    );
+    assert_eq!("About Weldmaster", i18n::ABOUT.as_str());
+    assert_eq!("Costs ${PRODUCT_NAME}", i18n::LITERAL.as_str());
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Schweissmeister 2000", i18n::PRODUCT_VERSION.as_str());
+    assert_eq!(
+        "Willkommen bei Schweissmeister 2000, John!",
+        i18n::WELCOME.format(("John",))
+    );
+    assert_eq!("About Schweissmeister", i18n::ABOUT.as_str());
+    assert_eq!("Costs ${PRODUCT_NAME}", i18n::LITERAL.as_str());
+}
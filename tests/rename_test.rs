@@ -0,0 +1,34 @@
+pub mod i18n {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[rename "error.404" => ERROR_NOT_FOUND]
+        English="tests/rename_english.properties";
+    }
+}
+
+pub mod modules {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[dotted_keys = modules]
+        #[rename "error.404" => NOT_FOUND]
+        English="tests/rename_english.properties";
+    }
+}
+
+#[test]
+pub fn test() {
+    assert_eq!("Not found", i18n::ERROR_NOT_FOUND.as_str());
+    assert_eq!("First place", i18n::FIRST_PLACE.as_str());
+    assert_eq!("Plain", i18n::plain.as_str());
+
+    assert_eq!("Not found", modules::error::NOT_FOUND.as_str());
+    assert_eq!("First place", modules::_1st::FIRST_PLACE.as_str());
+}
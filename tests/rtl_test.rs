@@ -0,0 +1,80 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        Arabic,
+        Hebrew,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[bidi_isolate = rtl]
+        English="tests/rtl_english.properties" [ltr];
+        Arabic="tests/rtl_arabic.properties" [rtl];
+        Hebrew="tests/rtl_english.properties", Arabic [rtl];
+    }
+}
+
+pub mod always {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/rtl_english.properties";
+        #[bidi_isolate = always]
+    }
+}
+
+//Languages can have the names of options.
+pub mod option_names {
+    #[allow(non_camel_case_types)]
+    pub enum Language {
+        bidi_isolate,
+        rtl,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        bidi_isolate="tests/rtl_english.properties";
+        rtl="tests/rtl_arabic.properties" [rtl];
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert!(!i18n::is_i18n_language_rtl());
+    assert_eq!("File a.txt saved.", i18n::FILE_SAVED.format(("a.txt",)));
+
+    i18n::set_i18n_language(Language::Arabic);
+    assert!(i18n::is_i18n_language_rtl());
+    assert_eq!(
+        "تم حفظ الملف \u{2068}a.txt\u{2069}.",
+        i18n::FILE_SAVED.format(("a.txt",))
+    );
+
+    let mut buffer = String::new();
+    i18n::FILE_SAVED
+        .format_into(("a.txt",), &mut buffer)
+        .expect("format_into failed");
+    assert_eq!("تم حفظ الملف \u{2068}a.txt\u{2069}.", buffer);
+
+    i18n::set_i18n_language(Language::Hebrew);
+    assert!(i18n::is_i18n_language_rtl());
+
+    assert!(!always::is_i18n_language_rtl());
+    assert_eq!(
+        "File \u{2068}a.txt\u{2069} saved.",
+        always::FILE_SAVED.format(("a.txt",))
+    );
+
+    option_names::set_i18n_language(option_names::Language::rtl);
+    assert!(option_names::is_i18n_language_rtl());
+    assert_eq!(
+        "تم حفظ الملف a.txt.",
+        option_names::FILE_SAVED.format(("a.txt",))
+    );
+}
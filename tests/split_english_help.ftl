@@ -0,0 +1,2 @@
+name = Help
+title = Open the { name }
@@ -0,0 +1,33 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/split_english_core.properties" + "tests/split_english_editor.properties" as "editor"
+            + "tests/split_english_help.ftl" as "help";
+        German="tests/split_german";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Hello John!", i18n::GREETING.format(("John",)));
+    assert_eq!("Save", i18n::SAVE.as_str());
+    assert_eq!("Open", i18n::_editor_DOT_OPEN.as_str());
+    assert_eq!("Close", i18n::_editor_DOT_CLOSE.as_str());
+    //References in a file with a prefix refer to the keys of the same file.
+    assert_eq!("About Tool", i18n::_editor_DOT_TITLE.as_str());
+    assert_eq!("Open the Help", i18n::_help_DOT_title.as_str());
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Hallo John!", i18n::GREETING.format(("John",)));
+    assert_eq!("Öffnen", i18n::_editor_DOT_OPEN.as_str());
+    assert_eq!("Close", i18n::_editor_DOT_CLOSE.as_str());
+    assert_eq!("Über Tool", i18n::_editor_DOT_TITLE.as_str());
+}
@@ -0,0 +1,64 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+        SwissGerman,
+        French,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[table = "tests/table.csv"]
+        English;
+        German;
+        SwissGerman, German;
+        French="tests/table.tsv" [column = "Français"];
+    }
+}
+
+pub mod derive {
+    #[derive(trivial_i18n::I18n)]
+    #[i18n(table = "tests/table.csv")]
+    pub enum Language {
+        #[i18n(default)]
+        English,
+        #[i18n(column = "SwissGerman", fallback = English)]
+        Swiss,
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Hello John!", i18n::GREETING.format(("John",)));
+    assert_eq!("Save", i18n::SAVE.as_str());
+    assert_eq!("Comma, \"quotes\" and\nnew line", i18n::QUOTED.as_str());
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Hallo John!", i18n::GREETING.format(("John",)));
+    assert_eq!("Speichern", i18n::SAVE.as_str());
+    assert_eq!(
+        "Komma, \"Anführungszeichen\" und\nneue Zeile",
+        i18n::QUOTED.as_str()
+    );
+    //Empty cells are not translated.
+    assert_eq!("New feature", i18n::NEW_FEATURE.as_str());
+
+    i18n::set_i18n_language(Language::SwissGerman);
+    assert_eq!("Grüezi John!", i18n::GREETING.format(("John",)));
+    assert_eq!("Speichern", i18n::SAVE.as_str());
+
+    i18n::set_i18n_language(Language::French);
+    assert_eq!("Bonjour John !", i18n::GREETING.format(("John",)));
+    assert_eq!("Enregistrer", i18n::SAVE.as_str());
+    assert_eq!("New feature", i18n::NEW_FEATURE.as_str());
+}
+
+#[test]
+pub fn test_derive() {
+    derive::set_i18n_language(derive::Language::Swiss);
+    assert_eq!("Grüezi John!", derive::GREETING.format(("John",)));
+    assert_eq!("Save", derive::SAVE.as_str());
+}
@@ -97,12 +97,13 @@ pub fn test() {
     );
 
     assert_eq!("123", i18n::_bad_DOT_key.as_str());
-    assert_eq!("456", i18n::_123.as_str());
+    assert_eq!("456", i18n::_123_821EACBA.as_str());
     assert_eq!("678", i18n::_123_0.as_str());
-    assert_eq!("123", i18n::_123_1.as_str());
+    //Colliding keys get a suffix with the hash of the key.
+    assert_eq!("123", i18n::_123_7238631B.as_str());
     assert_eq!("𝕊", i18n::𝕊.as_str());
-    assert_eq!("😀", i18n::___0.as_str());
-    assert_eq!("_", i18n::__.as_str());
+    assert_eq!("😀", i18n::___33A29608.as_str());
+    assert_eq!("_", i18n::___DA0C196E.as_str());
 
     i18n::set_i18n_language(Language::UsaEnglish);
     assert_eq!("Color", i18n::COLOR.as_str());
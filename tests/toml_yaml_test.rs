@@ -0,0 +1,42 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/toml_yaml_english.toml";
+        German="tests/toml_yaml_german.yaml";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Welcome", i18n::title.as_str());
+    assert_eq!("3", i18n::count.as_str());
+    assert_eq!("Quoted", i18n::_quoted_SPACE_key.as_str());
+    assert_eq!("Open", i18n::_menu_DOT_file_DOT_open.as_str());
+    assert_eq!(
+        "Save a.txt",
+        i18n::_menu_DOT_file_DOT_save.format(("a.txt",))
+    );
+    assert_eq!("First line\nsecond line", i18n::_help_DOT_text.as_str());
+    assert_eq!("C:\\Users\\john", i18n::_help_DOT_literal.format(("john",)));
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Willkommen", i18n::title.as_str());
+    assert_eq!("Open", i18n::_menu_DOT_file_DOT_open.as_str());
+    assert_eq!(
+        "a.txt speichern",
+        i18n::_menu_DOT_file_DOT_save.format(("a.txt",))
+    );
+    assert_eq!("Erste Zeile\nzweite Zeile", i18n::_help_DOT_text.as_str());
+    assert_eq!(
+        "C:\\Benutzer\\john",
+        i18n::_help_DOT_literal.format(("john",))
+    );
+}
@@ -0,0 +1,22 @@
+#![deny(dead_code)]
+
+pub mod i18n {
+    pub enum Language {
+        English,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[unused = warn]
+        #[visibility = pub(crate)]
+        #[naming = screaming_snake]
+        English="tests/unused_english.properties";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(i18n::Language::English);
+    assert_eq!("Hello", i18n::GREETING.as_str());
+    assert_eq!("Goodbye Bob", i18n::FAREWELL.format(("Bob",)));
+}
@@ -0,0 +1,22 @@
+//! Library that generates its items with `#[visibility = pub(crate)]`.
+//! Other crates can use its functions, but not the generated items.
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[visibility = pub(crate)]
+        English="tests/english.properties";
+        German="tests/german.properties";
+    }
+}
+
+/// Gets the translation of color in the given language.
+pub fn color(language: i18n::Language) -> &'static str {
+    i18n::set_i18n_language(language);
+    i18n::COLOR.as_str()
+}
@@ -0,0 +1,42 @@
+use crate::i18n::I18NLanguage;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[visibility = pub(crate)]
+        English="tests/english.properties";
+        German="tests/german.properties";
+    }
+}
+
+pub mod modules {
+    pub enum Language {
+        English,
+        German,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[visibility = pub(crate)]
+        #[dotted_keys = modules]
+        English="tests/modules_english.json";
+        German="tests/modules_german.properties";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(i18n::Language::German);
+    assert_eq!("German", i18n::Language::German.display_name());
+    assert!(!i18n::is_i18n_language_rtl());
+    assert_eq!("Farbe", i18n::COLOR.as_str());
+
+    modules::set_i18n_language(modules::Language::German);
+    assert_eq!("Willkommen", modules::title.as_str());
+    assert_eq!("Nicht gefunden", modules::error::_404.as_str());
+}
@@ -0,0 +1,60 @@
+use crate::i18n::Language;
+
+pub mod i18n {
+    pub enum Language {
+        English,
+        German,
+        French,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        English="tests/xliff_english.xlf";
+        German="tests/xliff_german.xml";
+        French="tests/xliff_french.xliff";
+    }
+}
+
+pub mod source {
+    pub enum Language {
+        English,
+        French,
+    }
+
+    trivial_i18n::i18n! {
+        Language;
+        #[untranslated = source]
+        English="tests/xliff_english.xlf";
+        French="tests/xliff_french.xliff";
+    }
+}
+
+#[test]
+pub fn test() {
+    i18n::set_i18n_language(Language::English);
+    assert_eq!("Hello, John!", i18n::GREETING.format(("John",)));
+    assert_eq!("Copied 3 files to /tmp.", i18n::COPIED.format((3, "/tmp")));
+    assert_eq!("Save", i18n::SAVE.as_str());
+    assert_eq!("Bold text", i18n::BOLD.as_str());
+    assert_eq!("New feature", i18n::NEW_FEATURE.as_str());
+
+    i18n::set_i18n_language(Language::German);
+    assert_eq!("Hallo, John!", i18n::GREETING.format(("John",)));
+    //The order of the arguments is defined by the default language.
+    assert_eq!("/tmp: 3 Dateien kopiert.", i18n::COPIED.format((3, "/tmp")));
+    assert_eq!("Speichern jetzt", i18n::SAVE.as_str());
+    //Segments in the initial state are not translated yet.
+    assert_eq!("New feature", i18n::NEW_FEATURE.as_str());
+
+    i18n::set_i18n_language(Language::French);
+    assert_eq!("Bonjour, John !", i18n::GREETING.format(("John",)));
+    assert_eq!("Copied 3 files to /tmp.", i18n::COPIED.format((3, "/tmp")));
+}
+
+#[test]
+pub fn test_untranslated_as_source() {
+    source::set_i18n_language(source::Language::French);
+    assert_eq!("Bonjour, John !", source::GREETING.format(("John",)));
+    assert_eq!("Save", source::SAVE.as_str());
+    assert_eq!("New feature", source::NEW_FEATURE.as_str());
+}